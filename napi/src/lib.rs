@@ -0,0 +1,111 @@
+use std::sync::{Arc, Mutex};
+
+use modularize_exports::{try_modularize_exports, Config};
+use napi::{Error, Result};
+use napi_derive::napi;
+use swc_common::{
+    comments::SingleThreadedComments,
+    errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+    sync::Lrc,
+    FileName, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_codegen::{text_writer::JsWriter, Config as CodegenConfig, Emitter as CodeEmitter};
+use swc_ecma_parser::{parse_file_as_module, EsConfig, Syntax};
+use swc_ecma_visit::FoldWith;
+
+/// Collects the messages of error diagnostics, so they can be thrown as a
+/// single JS exception.
+#[derive(Clone, Default)]
+struct ErrorCollector(Arc<Mutex<Vec<String>>>);
+
+impl Emitter for ErrorCollector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        if db.is_error() {
+            self.0.lock().unwrap().push(db.message());
+        }
+    }
+}
+
+/// Rewrites the exports of `source` according to `config`, a JSON config in
+/// the same shape as the SWC plugin's, and returns the emitted code.
+#[napi]
+pub fn transform(source: String, config: String) -> Result<String> {
+    let config =
+        Config::from_json_str(&config).map_err(|err| Error::from_reason(err.to_string()))?;
+    let mut folder =
+        try_modularize_exports(config).map_err(|err| Error::from_reason(err.to_string()))?;
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let comments = SingleThreadedComments::default();
+    let fm = cm.new_source_file(FileName::Anon, source);
+    let module = parse_file_as_module(
+        &fm,
+        Syntax::Es(EsConfig {
+            jsx: true,
+            import_attributes: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        Some(&comments),
+        &mut vec![],
+    )
+    .map_err(|err| Error::from_reason(format!("failed to parse: {}", err.kind().msg())))?;
+
+    let errors = ErrorCollector::default();
+    let handler = Handler::with_emitter(false, false, Box::new(errors.clone()));
+    let module = GLOBALS.set(&Globals::new(), || {
+        HANDLER.set(&handler, || module.fold_with(&mut folder))
+    });
+    let errors = errors.0.lock().unwrap();
+    if !errors.is_empty() {
+        return Err(Error::from_reason(errors.join("\n")));
+    }
+
+    let mut code = vec![];
+    let mut emitter = CodeEmitter {
+        cfg: CodegenConfig::default(),
+        cm: cm.clone(),
+        comments: Some(&comments),
+        wr: JsWriter::new(cm, "\n", &mut code, None),
+    };
+    emitter
+        .emit_module(&module)
+        .map_err(|err| Error::from_reason(err.to_string()))?;
+
+    String::from_utf8(code).map_err(|err| Error::from_reason(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::transform;
+
+    #[test]
+    fn transform_rewrites_exports_and_keeps_comments() {
+        let code = transform(
+            "// Components\nexport { Button } from 'react-bootstrap';".to_string(),
+            r#"{ "react-bootstrap": { "transform": "react-bootstrap/lib/{{ member }}" } }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            code,
+            "// Components\nexport * as Button from \"react-bootstrap/lib/Button\";\n"
+        );
+    }
+
+    #[test]
+    fn transform_throws_with_package_and_member() {
+        let err = transform(
+            "export { Button } from 'my-library';".to_string(),
+            r#"{ "my-library": { "transform": [["foo", "my-library/foo"]] } }"#.to_string(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.reason,
+            "missing transform for export 'Button' of package 'my-library'"
+        );
+    }
+}
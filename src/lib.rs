@@ -6,14 +6,12 @@ use swc_core::{
 
 #[plugin_transform]
 fn transform_exports_plugin(program: Program, data: TransformPluginProgramMetadata) -> Program {
-    let packages = serde_json::from_str(
+    let config = modularize_exports::Config::from_json_str(
         &data
             .get_transform_plugin_config()
             .expect("failed to get plugin config for transform-exports"),
     )
-    .expect("invalid packages");
+    .unwrap_or_else(|err| panic!("transform-exports: {}", err));
 
-    program.fold_with(&mut modularize_exports::modularize_exports(
-        modularize_exports::Config { packages },
-    ))
+    program.fold_with(&mut modularize_exports::modularize_exports(config))
 }
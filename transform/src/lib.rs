@@ -1,420 +1,2946 @@
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
 
-use convert_case::{Case, Casing};
-use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use convert_case::{Boundary, Case, Casing};
+use handlebars::{
+    Context, Handlebars, Helper, HelperDef, HelperResult, JsonValue, Output, RenderContext,
+    RenderError, ScopedJson,
+};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
-use serde::{Deserialize, Serialize};
-use swc_cached::regex::CachedRegex;
+use regex_syntax::hir::{Hir, HirKind};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use swc_cached::regex::{CachedRegex, Error as RegexError};
+use swc_common::{errors::HANDLER, Span, DUMMY_SP};
 use swc_ecma_ast::{ExportSpecifier, ModuleExportName, *};
-use swc_ecma_visit::{noop_fold_type, Fold};
+use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, Visit, VisitWith};
 
-static DUP_SLASH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"//").unwrap());
+// Consecutive slashes are collapsed unless they follow a colon, so that
+// scheme separators like `https://` survive.
+static DUP_SLASH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(^|[^:])//+").unwrap());
 
-#[derive(Clone, Debug, Deserialize)]
-#[serde(transparent)]
+// Environment variables read by the `env` helper, so that every file of a
+// compilation sees the same value.
+static ENV_VARS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(Default::default);
+
+/// The transform config.
+///
+/// Accepts either just the packages, or an object with the packages under
+/// `packages` next to the global options. Serializes to the latter.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Config {
-    pub packages: HashMap<String, PackageConfig>,
+    /// Package patterns, tried in order until one matches.
+    ///
+    /// Accepts either a map of pattern to config, or an array of
+    /// `[pattern, config]` pairs. The array form is always order-preserving;
+    /// the map form follows the order of the source document, which not every
+    /// producer guarantees. Serializes to the array form, so that the order
+    /// survives maps that sort their keys.
+    pub packages: Vec<(String, PackageConfig)>,
+    /// Quotes used for the generated sources.
+    pub quote_style: QuoteStyle,
+    /// Feeds each rewritten source back through the packages, so that a
+    /// package can further rewrite the output of another one. Packages that
+    /// match on exported names are not tried again.
+    pub chain: bool,
+    /// How many times a package may rewrite the output of its own rewrite
+    /// when chaining. Going deeper is reported as an error.
+    pub max_depth: usize,
+    /// Collapses duplicate slashes in rewritten sources, except after a `:`.
+    pub normalize_slashes: bool,
+    /// Sorts the exports rewritten from one statement by their new source.
+    /// Exports rewritten from different statements are never reordered.
+    pub sort_output: bool,
+    /// What templates do when they reference a missing variable, such as a
+    /// capture group index past the last group.
+    pub on_missing_variable: MissingVariableBehavior,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            packages: vec![],
+            quote_style: QuoteStyle::default(),
+            chain: false,
+            max_depth: default_max_depth(),
+            normalize_slashes: true,
+            sort_output: false,
+            on_missing_variable: MissingVariableBehavior::default(),
+        }
+    }
+}
+
+fn default_max_depth() -> usize {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl<'de> Deserialize<'de> for Config {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ConfigVisitor)
+    }
+}
+
+struct ConfigVisitor;
+
+impl<'de> Visitor<'de> for ConfigVisitor {
+    type Value = Config;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map or an array of packages, or an object with `packages`")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Ok(Config {
+            packages: packages_from_seq(seq)?,
+            ..Default::default()
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        // Which form this is only shows once the `packages` key is seen, so
+        // the other entries are kept as JSON until then.
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        let mut packages = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "packages" {
+                match map.next_value()? {
+                    PackagesField::List(list) => packages = Some(list),
+                    PackagesField::Package(value) => entries.push((key, value)),
+                }
+            } else {
+                entries.push((key, map.next_value()?));
+            }
+        }
+
+        let packages = match packages {
+            Some(packages) => packages,
+            None => {
+                return Ok(Config {
+                    packages: entries
+                        .into_iter()
+                        .map(package_from_value)
+                        .collect::<Result<_, _>>()?,
+                    ..Default::default()
+                })
+            }
+        };
+        let options: GlobalOptions =
+            serde_json::from_value(serde_json::Value::Object(entries.into_iter().collect()))
+                .map_err(de::Error::custom)?;
+        Ok(Config {
+            packages,
+            quote_style: options.quote_style,
+            chain: options.chain,
+            max_depth: options.max_depth,
+            normalize_slashes: options.normalize_slashes,
+            sort_output: options.sort_output,
+            on_missing_variable: options.on_missing_variable,
+        })
+    }
+}
+
+/// The options next to `packages` in the object form of [Config].
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GlobalOptions {
+    #[serde(default)]
+    quote_style: QuoteStyle,
+    #[serde(default)]
+    chain: bool,
+    #[serde(default = "default_max_depth")]
+    max_depth: usize,
+    #[serde(default = "default_true")]
+    normalize_slashes: bool,
+    #[serde(default)]
+    sort_output: bool,
+    #[serde(default)]
+    on_missing_variable: MissingVariableBehavior,
+}
+
+/// The value of a `packages` key: either the packages of the object form, or
+/// the config of a package that happens to be named `packages`, told apart
+/// by its `transform`.
+enum PackagesField {
+    List(Vec<(String, PackageConfig)>),
+    Package(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for PackagesField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PackagesFieldVisitor;
+
+        impl<'de> Visitor<'de> for PackagesFieldVisitor {
+            type Value = PackagesField;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map or an array of [pattern, config] pairs")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries: Vec<(String, serde_json::Value)> =
+                    Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                if entries.iter().any(|(key, _)| key == "transform") {
+                    return Ok(PackagesField::Package(serde_json::Value::Object(
+                        entries.into_iter().collect(),
+                    )));
+                }
+                Ok(PackagesField::List(
+                    entries
+                        .into_iter()
+                        .map(package_from_value)
+                        .collect::<Result<_, _>>()?,
+                ))
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                packages_from_seq(seq).map(PackagesField::List)
+            }
+        }
+
+        deserializer.deserialize_any(PackagesFieldVisitor)
+    }
+}
+
+fn packages_from_seq<'de, A>(mut seq: A) -> Result<Vec<(String, PackageConfig)>, A::Error>
+where
+    A: SeqAccess<'de>,
+{
+    let mut packages = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+    while let Some(entry) = seq.next_element()? {
+        packages.push(entry);
+    }
+    Ok(packages)
+}
+
+/// Deserializes a package kept as JSON, naming the package in the error
+/// since its position in the document is lost by then.
+fn package_from_value<E>(
+    (key, value): (String, serde_json::Value),
+) -> Result<(String, PackageConfig), E>
+where
+    E: de::Error,
+{
+    match PackageConfig::deserialize(value) {
+        Ok(package) => Ok((key, package)),
+        Err(err) => Err(E::custom(format_args!("package '{}': {}", key, err))),
+    }
+}
+
+/// The quote character of generated string literals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuoteStyle {
+    #[default]
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    /// Creates a string literal printed with these quotes.
+    fn str(self, value: &str) -> Str {
+        if self == QuoteStyle::Double {
+            // Without a raw form the printer quotes the value itself, with its
+            // default double quotes, instead of reusing the quoting of the
+            // original source.
+            return Str {
+                span: DUMMY_SP,
+                value: value.into(),
+                raw: None,
+            };
+        }
+        let mut raw = String::with_capacity(value.len() + 2);
+        raw.push('\'');
+        for c in value.chars() {
+            if c == '\'' || c == '\\' {
+                raw.push('\\');
+            }
+            raw.push(c);
+        }
+        raw.push('\'');
+        Str {
+            span: DUMMY_SP,
+            value: value.into(),
+            raw: Some(raw.into()),
+        }
+    }
+}
+
+impl Config {
+    /// Parses a JSON config and checks that every package key and
+    /// `Transform::Vec` pattern is a valid regex, so that misconfiguration is
+    /// reported before the first fold.
+    pub fn from_json_str(s: &str) -> Result<Config, TransformError> {
+        let config: Config =
+            serde_json::from_str(s).map_err(|source| TransformError::InvalidConfig { source })?;
+        config.validate()
+    }
+
+    /// Like [Config::from_json_str], but for a config that was already parsed
+    /// or built up as JSON, e.g. by merging several sources.
+    ///
+    /// A `serde_json::Value` map keeps its keys sorted, so packages given in
+    /// the map form are tried in alphabetical order rather than in document
+    /// order. Use the `[pattern, config]` array form to keep their priority.
+    pub fn from_value(value: serde_json::Value) -> Result<Config, TransformError> {
+        let config: Config = serde_json::from_value(value)
+            .map_err(|source| TransformError::InvalidConfig { source })?;
+        config.validate()
+    }
+
+    fn validate(self) -> Result<Config, TransformError> {
+        for (key, package) in &self.packages {
+            compile_package_key(key, package)?;
+            CompiledTransform::new(package)?;
+        }
+        Ok(self)
+    }
+
+    /// Like [Config::from_json_str], but also rejects unknown package options,
+    /// so that a typo such as `preventFullExports` is reported instead of
+    /// being ignored.
+    pub fn from_json_str_strict(s: &str) -> Result<Config, TransformError> {
+        let config = Config::from_json_str(s)?;
+        let invalid = |msg: String| TransformError::InvalidConfig {
+            source: serde::de::Error::custom(msg),
+        };
+
+        let value: serde_json::Value =
+            serde_json::from_str(s).map_err(|source| TransformError::InvalidConfig { source })?;
+        // Tell the two forms apart the same way `Config`'s `Deserialize` does.
+        let packages = match value.get("packages") {
+            Some(packages) if packages.get("transform").is_none() => packages,
+            _ => &value,
+        };
+        let packages: Vec<(&str, &serde_json::Value)> = match packages {
+            serde_json::Value::Object(map) => map.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+            serde_json::Value::Array(pairs) => pairs
+                .iter()
+                .filter_map(|pair| Some((pair.get(0)?.as_str()?, pair.get(1)?)))
+                .collect(),
+            _ => vec![],
+        };
+
+        let known = serde_json::to_value(PackageConfig::default()).unwrap();
+        for (key, package) in packages {
+            if let Some(fields) = package.as_object() {
+                if let Some(field) = fields.keys().find(|field| known.get(field).is_none()) {
+                    return Err(invalid(format!(
+                        "unknown field `{}` of package '{}'",
+                        field, key
+                    )));
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// Combines a base config with an override. The packages of `other` come
+    /// first, in their own order, so they are tried before the base ones, and
+    /// replace base packages with the same key.
+    ///
+    /// A global option is taken from `other` where it differs from the
+    /// default, and from `self` otherwise, so an override cannot reset an
+    /// option of the base back to its default.
+    pub fn merge(self, other: Config) -> Config {
+        let mut packages = other.packages;
+        for (key, package) in self.packages {
+            if !packages.iter().any(|(k, _)| *k == key) {
+                packages.push((key, package));
+            }
+        }
+        let default = Config::default();
+        fn pick<T: PartialEq>(base: T, other: T, default: &T) -> T {
+            if other != *default {
+                other
+            } else {
+                base
+            }
+        }
+        Config {
+            packages,
+            quote_style: pick(self.quote_style, other.quote_style, &default.quote_style),
+            chain: pick(self.chain, other.chain, &default.chain),
+            max_depth: pick(self.max_depth, other.max_depth, &default.max_depth),
+            normalize_slashes: pick(
+                self.normalize_slashes,
+                other.normalize_slashes,
+                &default.normalize_slashes,
+            ),
+            sort_output: pick(self.sort_output, other.sort_output, &default.sort_output),
+            on_missing_variable: pick(
+                self.on_missing_variable,
+                other.on_missing_variable,
+                &default.on_missing_variable,
+            ),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PackageConfig {
     pub transform: Transform,
     #[serde(default)]
     pub prevent_full_export: bool,
+    /// Keeps rewritten named specifiers as `export { x } from` instead of
+    /// converting them to `export * as x from`. `export * from` has nothing to
+    /// convert, so it is rewritten to `export * from` either way.
     #[serde(default)]
     pub skip_default_conversion: bool,
+    #[serde(default)]
+    pub on_missing: MissingBehavior,
+    /// A regex matched against the member whose captures are exposed as
+    /// `memberMatches` to a `Transform::String` template.
+    #[serde(default)]
+    pub member_pattern: Option<String>,
+    /// Literal text prepended to every rendered path.
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Literal text appended to every rendered path.
+    #[serde(default)]
+    pub suffix: Option<String>,
+    /// File extension appended to every rendered path that does not already
+    /// end with it, e.g. `.js`.
+    #[serde(default)]
+    pub extension: Option<String>,
+    /// Extensions stripped off the end of the source before its captures are
+    /// exposed as `matches`, e.g. `.jsx`.
+    #[serde(default)]
+    pub strip_extensions: Vec<String>,
+    /// Emit an `import` of a fresh local binding followed by a local `export`
+    /// instead of an `export ... from` for every rewritten declaration.
+    #[serde(default)]
+    pub emit_import_export: bool,
+    /// Match the package pattern regardless of case.
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// Regexes of members that are re-exported from the original source
+    /// instead of being rewritten.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Regexes of the only members that are rewritten; all others are
+    /// re-exported from the original source. An empty list rewrites nothing.
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Template used for members no `Transform::Vec` pattern or
+    /// `Transform::Map` key matches, before `on_missing` applies.
+    #[serde(default)]
+    pub fallback: Option<String>,
+    /// Coalesce rewritten exports of the same path into a single export. Only
+    /// applies with `skip_default_conversion`, as namespace exports cannot be
+    /// combined. With a template that does not use `member`, every member is
+    /// re-exported from that one path in a single export.
+    #[serde(default)]
+    pub merge_same_source: bool,
+    /// How to pick among several matching `Transform::Vec` rules.
+    #[serde(default)]
+    pub match_strategy: MatchStrategy,
+    /// Transform for `export * from`, rendered with `*` as the member. Uses
+    /// `transform` when unset.
+    #[serde(default)]
+    pub all_transform: Option<Transform>,
+    /// How the package key is interpreted.
+    #[serde(default)]
+    pub match_mode: MatchMode,
+    /// Adds the `^` and `$` anchors a regex package key lacks. When `false`,
+    /// the key is used as is, so that e.g. `react-` matches any source
+    /// containing it. Glob keys are always anchored.
+    #[serde(default = "default_true")]
+    pub anchored: bool,
+    /// Arbitrary JSON exposed to templates as `data`, e.g. a table of special
+    /// cases read with `{{ lookup data member }}`.
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+    /// Routes a re-exported `default` by its alias, so that
+    /// `export { default as Button }` renders with `Button` as the member. An
+    /// unaliased `default` renders with this placeholder instead.
+    #[serde(default)]
+    pub default_member: Option<String>,
+    /// What the package key is matched against.
+    #[serde(default)]
+    pub match_on: MatchTarget,
+    /// A note about the package, ignored by the transform.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The members the package exports, e.g. harvested from its typings. When
+    /// set, `export * from` is expanded into a named re-export of each of them,
+    /// rewritten like any other.
+    #[serde(default)]
+    pub known_members: Option<Vec<String>>,
+    /// Where the case helpers split members into words, instead of the
+    /// boundaries of each case. Exposed to templates as `caseBoundaries`.
+    #[serde(default)]
+    pub case_boundaries: Option<Vec<CaseBoundary>>,
+}
+
+impl Default for PackageConfig {
+    fn default() -> Self {
+        PackageConfig {
+            transform: Transform::String(String::new()),
+            prevent_full_export: false,
+            skip_default_conversion: false,
+            on_missing: MissingBehavior::default(),
+            member_pattern: None,
+            prefix: None,
+            suffix: None,
+            extension: None,
+            strip_extensions: Vec::new(),
+            emit_import_export: false,
+            case_insensitive: false,
+            exclude: Vec::new(),
+            include: None,
+            fallback: None,
+            merge_same_source: false,
+            match_strategy: MatchStrategy::default(),
+            all_transform: None,
+            match_mode: MatchMode::default(),
+            anchored: true,
+            data: None,
+            default_member: None,
+            match_on: MatchTarget::default(),
+            description: None,
+            known_members: None,
+            case_boundaries: None,
+        }
+    }
+}
+
+/// What to do when no `Transform::Vec` pattern or `Transform::Map` key
+/// matches an exported member.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MissingBehavior {
+    /// Fail the export with [TransformError::MissingTransform], keeping it
+    /// as is and reporting the error.
+    #[default]
+    Panic,
+    /// Leave the original specifier untouched.
+    Keep,
+    /// Emit a warning and leave the original specifier untouched.
+    Warn,
+}
+
+/// What a template renders for a variable that is not in its context, such
+/// as `{{ matches.[3] }}` when the package pattern has two groups.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MissingVariableBehavior {
+    /// Render nothing in its place.
+    #[default]
+    Empty,
+    /// Fail the export with [TransformError::RenderFailed].
+    Error,
+}
+
+/// How a `Transform::Vec` rule is picked when several patterns match a member.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchStrategy {
+    /// The first matching rule, in declaration order.
+    #[default]
+    First,
+    /// The most specific matching rule, i.e. the one whose pattern requires the
+    /// most literal characters, so `useTheme` beats `use(\w*)`, which beats
+    /// `.*`. Ties go to the first declared.
+    LongestMatch,
+}
+
+/// The syntax of a package key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchMode {
+    #[default]
+    Regex,
+    /// `*` and `?` match within a path segment and `**` across segments, each
+    /// exposed as a capture in `matches`. A trailing `/**` also matches the
+    /// package root.
+    Glob,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// What a package pattern is matched against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchTarget {
+    /// The source of the export.
+    #[default]
+    Source,
+    /// The exported name of each specifier, whatever the source. Only the
+    /// matching specifiers are rewritten, with the captures of their name as
+    /// `matches`; the others go to the next package that claims them. Never
+    /// matches `export * from`.
+    ExportedName,
+}
+
+/// Where the case helpers split a member into words, after convert_case's
+/// boundaries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaseBoundary {
+    /// `a-b`
+    Hyphen,
+    /// `a_b`
+    Underscore,
+    /// `a b`
+    Space,
+    /// `Ab`, between the two letters.
+    UpperLower,
+    /// `aB`
+    LowerUpper,
+    /// `1A`
+    DigitUpper,
+    /// `A1`
+    UpperDigit,
+    /// `1a`
+    DigitLower,
+    /// `a1`
+    LowerDigit,
+    /// `HTTPRequest`, between `HTTP` and `Request`.
+    Acronym,
+}
+
+impl CaseBoundary {
+    fn boundary(self) -> Boundary {
+        match self {
+            CaseBoundary::Hyphen => Boundary::Hyphen,
+            CaseBoundary::Underscore => Boundary::Underscore,
+            CaseBoundary::Space => Boundary::Space,
+            CaseBoundary::UpperLower => Boundary::UpperLower,
+            CaseBoundary::LowerUpper => Boundary::LowerUpper,
+            CaseBoundary::DigitUpper => Boundary::DigitUpper,
+            CaseBoundary::UpperDigit => Boundary::UpperDigit,
+            CaseBoundary::DigitLower => Boundary::DigitLower,
+            CaseBoundary::LowerDigit => Boundary::LowerDigit,
+            CaseBoundary::Acronym => Boundary::Acronym,
+        }
+    }
+}
+
+/// The template for the new path of each member. For `export * from`, a
+/// template that renders to several lines produces one export per non-empty
+/// line. A named member can only be bound once, so it must render to a single
+/// line.
+#[derive(Clone, Debug, Serialize)]
 #[serde(untagged)]
 pub enum Transform {
     String(String),
+    /// `(pattern, template)` rules, tried in order. Each rule is accepted as
+    /// either a `[pattern, template]` pair or a `{ pattern, template }` object.
     Vec(Vec<(String, String)>),
+    /// Templates keyed by the exact member name, for literal names that need
+    /// no pattern.
+    Map(HashMap<String, String>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TransformRule {
+    Pair(String, String),
+    Struct { pattern: String, template: String },
+}
+
+impl<'de> Deserialize<'de> for Transform {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TransformVisitor;
+
+        impl<'de> Visitor<'de> for TransformVisitor {
+            type Value = Transform;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(
+                    "a `transform` template, an array of [pattern, template] rules or a map of \
+                     member to template",
+                )
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Transform::String(v.to_string()))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut rules = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(rule) = seq.next_element()? {
+                    rules.push(match rule {
+                        TransformRule::Pair(pattern, template) => (pattern, template),
+                        TransformRule::Struct { pattern, template } => (pattern, template),
+                    });
+                }
+                Ok(Transform::Vec(rules))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut templates = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((member, template)) = map.next_entry()? {
+                    templates.insert(member, template);
+                }
+                Ok(Transform::Map(templates))
+            }
+        }
+
+        deserializer.deserialize_any(TransformVisitor)
+    }
+}
+
+impl From<&str> for Transform {
+    fn from(s: &str) -> Self {
+        Transform::String(s.to_string())
+    }
+}
+impl From<Vec<(String, String)>> for Transform {
+    fn from(v: Vec<(String, String)>) -> Self {
+        Transform::Vec(v)
+    }
+}
+impl From<HashMap<String, String>> for Transform {
+    fn from(m: HashMap<String, String>) -> Self {
+        Transform::Map(m)
+    }
+}
+
+/// An error raised while rewriting an export, carrying enough context to
+/// tell which package and member caused it.
+#[derive(Debug)]
+pub enum TransformError {
+    /// None of the `Transform::Vec` patterns or `Transform::Map` keys matched
+    /// the exported member.
+    MissingTransform { package: String, member: String },
+    /// The transform template could not be rendered for the member.
+    RenderFailed {
+        package: String,
+        member: String,
+        source: Box<RenderError>,
+    },
+    /// A package key or `Transform::Vec` pattern is not a valid regex.
+    /// `pattern` is the key or pattern as written in the config.
+    InvalidRegex { pattern: String, source: RegexError },
+    /// The config is not valid JSON or does not have the expected shape.
+    InvalidConfig { source: serde_json::Error },
+    /// The chained `source` matched `package`, which already rewrote it
+    /// `max_depth` times.
+    MaxDepthExceeded {
+        package: String,
+        source: String,
+        max_depth: usize,
+    },
+    /// A specifier that cannot be rewritten on its own, such as
+    /// `export v from 'pkg'`, with `prevent_full_export` set.
+    FullExport { package: String, specifier: String },
+    /// The template rendered several paths for a named member, which would
+    /// export the same name more than once.
+    DuplicateExport {
+        package: String,
+        member: String,
+        paths: usize,
+    },
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformError::MissingTransform { package, member } => write!(
+                f,
+                "missing transform for export '{}' of package '{}'",
+                member, package
+            ),
+            TransformError::RenderFailed {
+                package,
+                member,
+                source,
+            } => write!(
+                f,
+                "error rendering template for export '{}' of package '{}': {}",
+                member, package, source
+            ),
+            TransformError::InvalidRegex { pattern, source } => {
+                write!(f, "invalid regex '{}': {}", pattern, source)
+            }
+            TransformError::InvalidConfig { source } => write!(f, "invalid config: {}", source),
+            TransformError::FullExport { package, specifier } => write!(
+                f,
+                "export '{}' of package '{}' causes the entire module to be exported",
+                specifier, package
+            ),
+            TransformError::DuplicateExport {
+                package,
+                member,
+                paths,
+            } => write!(
+                f,
+                "export '{}' of package '{}' renders to {} paths, but a name can only be exported \
+                 once",
+                member, package, paths
+            ),
+            TransformError::MaxDepthExceeded {
+                package,
+                source,
+                max_depth,
+            } => write!(
+                f,
+                "rewritten source '{}' matches package '{}', which already rewrote it {} time(s)",
+                source, package, max_depth
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransformError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TransformError::MissingTransform { .. } => None,
+            TransformError::MaxDepthExceeded { .. } => None,
+            TransformError::FullExport { .. } => None,
+            TransformError::DuplicateExport { .. } => None,
+            TransformError::RenderFailed { source, .. } => Some(source.as_ref()),
+            TransformError::InvalidRegex { source, .. } => Some(source.as_ref()),
+            TransformError::InvalidConfig { source } => Some(source),
+        }
+    }
+}
+
+/// Reports `err` through the swc diagnostics handler, if one is installed, and
+/// records it on `errors`, so that a single bad package config does not abort
+/// the whole compilation.
+fn report_error(errors: &ErrorsHandle, span: Span, err: TransformError) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.struct_span_err(span, &err.to_string()).emit());
+    }
+    errors.0.lock().unwrap().push(err);
+}
+
+/// Reports `err` as a warning through the swc diagnostics handler. Nothing is
+/// reported when no handler is installed, as the export is kept anyway.
+fn report_warning(span: Span, err: &TransformError) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.struct_span_warn(span, &err.to_string()).emit());
+    }
+}
+
+/// The fold built by [modularize_exports], nameable so it can be stored or
+/// boxed alongside other folds.
+pub struct FoldExports {
+    renderer: Arc<handlebars::Handlebars<'static>>,
+    packages: Arc<Vec<Package>>,
+    quote_style: QuoteStyle,
+    chain: bool,
+    max_depth: usize,
+    dirty: DirtyHandle,
+    errors: ErrorsHandle,
+    preview: Option<PreviewSink>,
+    stats: Option<StatsHandle>,
+    normalize_slashes: bool,
+    sort_output: bool,
+}
+
+/// An export rewrite planned by a preview fold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedRewrite {
+    /// The source of the original export.
+    pub src: String,
+    /// The exported name of the member, or `*` for `export * from`.
+    pub member: String,
+    /// The source the member would be exported from.
+    pub new_src: String,
 }
 
-impl From<&str> for Transform {
-    fn from(s: &str) -> Self {
-        Transform::String(s.to_string())
-    }
-}
-impl From<Vec<(String, String)>> for Transform {
-    fn from(v: Vec<(String, String)>) -> Self {
-        Transform::Vec(v)
-    }
+/// Collects the rewrites planned by a [FoldExports] created with
+/// [modularize_exports_preview].
+///
+/// Clones share the same list.
+#[derive(Clone, Debug, Default)]
+pub struct PreviewSink(Arc<Mutex<Vec<PlannedRewrite>>>);
+
+impl PreviewSink {
+    /// Returns the rewrites planned so far.
+    pub fn rewrites(&self) -> Vec<PlannedRewrite> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Returns the rewrites planned so far, clearing the list.
+    pub fn take(&self) -> Vec<PlannedRewrite> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+
+    fn record(&self, src: &str, member: &str, new_src: &str) {
+        if src != new_src {
+            self.0.lock().unwrap().push(PlannedRewrite {
+                src: src.to_string(),
+                member: member.to_string(),
+                new_src: new_src.to_string(),
+            });
+        }
+    }
+}
+
+/// Tells whether a [FoldExports] changed any export of the modules it folded.
+///
+/// Clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct DirtyHandle(Arc<AtomicBool>);
+
+impl DirtyHandle {
+    /// Whether an export was rewritten since the last [DirtyHandle::reset].
+    pub fn is_dirty(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Clears the flag, e.g. before folding the next file.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    /// Sets the flag unless `rewritten` is just `decl`.
+    fn track<T: PartialEq>(&self, decl: &T, rewritten: &[T]) {
+        if !matches!(rewritten, [x] if x == decl) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Collects the errors of the exports a [FoldExports] had to keep as is, for
+/// callers that fold without a swc diagnostics handler.
+///
+/// Clones share the same list.
+#[derive(Clone, Debug, Default)]
+pub struct ErrorsHandle(Arc<Mutex<Vec<TransformError>>>);
+
+impl ErrorsHandle {
+    /// Whether any export failed since the last [ErrorsHandle::take].
+    pub fn has_errors(&self) -> bool {
+        !self.0.lock().unwrap().is_empty()
+    }
+
+    /// Returns the errors reported so far, clearing the list.
+    pub fn take(&self) -> Vec<TransformError> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+/// Counts the members rewritten by a [FoldExports] created with
+/// [modularize_exports_with_stats], keyed by package.
+///
+/// Clones share the same counts.
+#[derive(Clone, Debug, Default)]
+pub struct StatsHandle(Arc<Mutex<HashMap<String, usize>>>);
+
+impl StatsHandle {
+    /// Returns the number of members rewritten by each package so far.
+    /// Packages that rewrote nothing are left out.
+    pub fn counts(&self) -> HashMap<String, usize> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Clears the counts, e.g. before folding the next file.
+    pub fn reset(&self) {
+        self.0.lock().unwrap().clear();
+    }
+
+    fn add(&self, package: &str, count: usize) {
+        if count > 0 {
+            *self
+                .0
+                .lock()
+                .unwrap()
+                .entry(package.to_string())
+                .or_default() += count;
+        }
+    }
+}
+
+/// A package config with all of its patterns compiled up front, so that the
+/// hot path only has to run `captures`.
+struct Package {
+    key: String,
+    regex: CachedRegex,
+    config: PackageConfig,
+    compiled: CompiledTransform,
+}
+
+/// The member patterns of a [PackageConfig], anchored and compiled.
+struct CompiledTransform {
+    rules: Vec<Rule>,
+    all_rules: Vec<Rule>,
+    member_regex: Option<CachedRegex>,
+    exclude: Vec<CachedRegex>,
+    include: Option<Vec<CachedRegex>>,
+}
+
+/// A compiled `Transform::Vec` rule.
+struct Rule {
+    regex: CachedRegex,
+    template: String,
+    /// Whether the pattern is the literal `*` key.
+    wildcard: bool,
+    /// The number of literal characters every match of the pattern contains,
+    /// used to rank rules under [MatchStrategy::LongestMatch].
+    specificity: usize,
+}
+
+fn compile_rules(transform: &Transform) -> Result<Vec<Rule>, TransformError> {
+    match transform {
+        Transform::String(_) | Transform::Map(_) => Ok(vec![]),
+        Transform::Vec(patterns) => patterns
+            .iter()
+            .map(|(pattern, template)| {
+                // A bare `*` is not a valid regex on its own, but matches
+                // every member as a rule.
+                let regex = if pattern == "*" { ".*" } else { pattern };
+                Ok(Rule {
+                    regex: compile_pattern(regex, false)?,
+                    template: template.clone(),
+                    wildcard: pattern == "*",
+                    specificity: regex_syntax::parse(pattern).map_or(0, |hir| literal_chars(&hir)),
+                })
+            })
+            .collect(),
+    }
+}
+
+impl CompiledTransform {
+    fn new(config: &PackageConfig) -> Result<Self, TransformError> {
+        let rules = compile_rules(&config.transform)?;
+        let all_rules = match &config.all_transform {
+            Some(transform) => compile_rules(transform)?,
+            None => vec![],
+        };
+        let member_regex = match &config.member_pattern {
+            Some(pattern) => Some(compile_pattern(pattern, false)?),
+            None => None,
+        };
+        let exclude = config
+            .exclude
+            .iter()
+            .map(|pattern| compile_pattern(pattern, false))
+            .collect::<Result<_, TransformError>>()?;
+        let include = match &config.include {
+            Some(patterns) => Some(
+                patterns
+                    .iter()
+                    .map(|pattern| compile_pattern(pattern, false))
+                    .collect::<Result<_, TransformError>>()?,
+            ),
+            None => None,
+        };
+        Ok(CompiledTransform {
+            rules,
+            all_rules,
+            member_regex,
+            exclude,
+            include,
+        })
+    }
+}
+
+struct Rewriter<'a> {
+    /// The fold, to find the packages after this one.
+    folder: &'a FoldExports,
+    /// Index of the matched package.
+    index: usize,
+    renderer: &'a handlebars::Handlebars<'static>,
+    key: &'a str,
+    regex: &'a CachedRegex,
+    config: &'a PackageConfig,
+    compiled: &'a CompiledTransform,
+    group: Groups<'a>,
+    quote_style: QuoteStyle,
+    normalize_slashes: bool,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(untagged)]
+enum Data<'a> {
+    Plain(&'a str),
+    Bool(bool),
+    Number(usize),
+    Groups(&'a Groups<'a>),
+    Json(&'a serde_json::Value),
+    Boundaries(&'a [CaseBoundary]),
+}
+
+/// The capture groups of a regex match. Exposed to templates as an array, or
+/// as a map keyed by both index and name when the regex has named groups.
+#[derive(Default)]
+struct Groups<'a> {
+    positional: Vec<&'a str>,
+    named: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Groups<'a> {
+    fn new(regex: &'a Regex, captures: &Captures<'a>) -> Self {
+        Groups {
+            positional: captures
+                .iter()
+                .map(|x| x.map(|x| x.as_str()).unwrap_or_default())
+                .collect(),
+            named: regex
+                .capture_names()
+                .flatten()
+                .map(|name| {
+                    let value = captures.name(name).map(|x| x.as_str());
+                    (name, value.unwrap_or_default())
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Serialize for Groups<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.named.is_empty() {
+            return self.positional.serialize(serializer);
+        }
+        let mut map = serializer.serialize_map(Some(self.positional.len() + self.named.len()))?;
+        for (index, value) in self.positional.iter().enumerate() {
+            map.serialize_entry(&index.to_string(), value)?;
+        }
+        for (name, value) in &self.named {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+fn export_name_str(name: &ModuleExportName) -> &str {
+    match name {
+        ModuleExportName::Ident(x) => x.as_ref(),
+        ModuleExportName::Str(x) => x.value.as_ref(),
+    }
+}
+
+/// Returns the string-valued import attributes of a `with { ... }` clause.
+fn import_attributes(with: Option<&ObjectLit>) -> Vec<(&str, &str)> {
+    with.map_or(&[][..], |with| &with.props)
+        .iter()
+        .filter_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(KeyValueProp { key, value }) => {
+                    let key = match key {
+                        PropName::Ident(x) => &*x.sym,
+                        PropName::Str(x) => &*x.value,
+                        _ => return None,
+                    };
+                    match &**value {
+                        Expr::Lit(Lit::Str(x)) => Some((key, &*x.value)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the name a specifier binds in the exporting module.
+fn exported_name_str(spec: &ExportSpecifier) -> &str {
+    match spec {
+        ExportSpecifier::Named(x) => export_name_str(x.exported.as_ref().unwrap_or(&x.orig)),
+        ExportSpecifier::Namespace(x) => export_name_str(&x.name),
+        ExportSpecifier::Default(x) => x.exported.as_ref(),
+    }
+}
+
+/// Resolves the new path of `member` of `package`, using the same template
+/// logic as the fold. Multiple paths are separated by newlines. `groups` are
+/// the captures of the package pattern, exposed to the template as `matches`.
+///
+/// `renderer` should come from [renderer] so the built-in helpers are
+/// available.
+pub fn transform_specifier(
+    config: &PackageConfig,
+    renderer: &Handlebars,
+    package: &str,
+    member: &str,
+    groups: &[&str],
+) -> Result<String, TransformError> {
+    let compiled = CompiledTransform::new(config)?;
+    let member_ctx = Member {
+        name: member,
+        exported: member,
+        all: false,
+        position: None,
+        with: &[],
+    };
+    let groups = Groups {
+        positional: groups.to_vec(),
+        named: vec![],
+    };
+    render_path(
+        renderer,
+        config,
+        &compiled,
+        package,
+        &member_ctx,
+        &groups,
+        true,
+    )?
+    .ok_or_else(|| TransformError::MissingTransform {
+        package: package.to_string(),
+        member: member.to_string(),
+    })
+}
+
+/// The exported member being rewritten.
+struct Member<'a> {
+    /// The name of the member in the source package.
+    name: &'a str,
+    /// The name the member is re-exported as.
+    exported: &'a str,
+    /// Whether this is the `*` of an `export * from`.
+    all: bool,
+    /// The zero-based index of the specifier in its export statement, and the
+    /// number of specifiers in it. Only set for named exports.
+    position: Option<(usize, usize)>,
+    /// The import attributes of the export statement, exposed to templates
+    /// as `with`.
+    with: &'a [(&'a str, &'a str)],
+}
+
+/// Renders the new path for `member`, or returns `None` when no
+/// `Transform::Vec` pattern or `Transform::Map` key matches it and there is
+/// no `fallback`.
+fn render_path(
+    renderer: &Handlebars,
+    config: &PackageConfig,
+    compiled: &CompiledTransform,
+    package: &str,
+    member: &Member,
+    groups: &Groups,
+    normalize_slashes: bool,
+) -> Result<Option<String>, TransformError> {
+    let Member {
+        name,
+        exported,
+        all,
+        position,
+        with,
+    } = *member;
+    let member = match &config.default_member {
+        Some(_) if name == "default" && exported != "default" => exported,
+        Some(placeholder) if name == "default" => placeholder,
+        _ => name,
+    };
+
+    let render_failed = |source| TransformError::RenderFailed {
+        package: package.to_string(),
+        member: member.to_string(),
+        source: Box::new(source),
+    };
+
+    let member_matches = compiled
+        .member_regex
+        .as_ref()
+        .and_then(|regex| Some(Groups::new(regex, &regex.captures(member)?)));
+
+    let with = (!with.is_empty()).then(|| {
+        serde_json::Value::Object(
+            with.iter()
+                .map(|(k, v)| (k.to_string(), serde_json::Value::from(*v)))
+                .collect(),
+        )
+    });
+
+    let mut ctx: HashMap<&str, Data> = HashMap::new();
+    ctx.insert("matches", Data::Groups(groups));
+    ctx.insert("member", Data::Plain(member));
+    ctx.insert("exportedName", Data::Plain(exported));
+    ctx.insert("packageName", Data::Plain(package));
+    ctx.insert("isDefault", Data::Bool(name == "default"));
+    if let Some((index, total)) = position {
+        ctx.insert("index", Data::Number(index));
+        ctx.insert("total", Data::Number(total));
+    }
+    if let Some(data) = &config.data {
+        ctx.insert("data", Data::Json(data));
+    }
+    if let Some(with) = &with {
+        ctx.insert("with", Data::Json(with));
+    }
+    if let Some(boundaries) = &config.case_boundaries {
+        ctx.insert("caseBoundaries", Data::Boundaries(boundaries));
+    }
+
+    let (transform, rules) = match &config.all_transform {
+        Some(all_transform) if all => (all_transform, &compiled.all_rules),
+        _ => (&config.transform, &compiled.rules),
+    };
+    // Without an `all_transform`, `export *` only goes through an explicit `*`
+    // rule, never through a member rule that happens to match the asterisk.
+    let wildcard_only = all && config.all_transform.is_none();
+
+    let new_path = match transform {
+        Transform::String(s) => {
+            if let Some(group) = &member_matches {
+                ctx.insert("memberMatches", Data::Groups(group));
+            }
+            renderer.render_template(s, &ctx).map_err(render_failed)?
+        }
+        Transform::Map(map) => {
+            let template = match (map.get(member), &config.fallback) {
+                (Some(template), _) => template,
+                (None, Some(fallback)) if !wildcard_only => fallback,
+                (None, _) => return Ok(None),
+            };
+            if let Some(group) = &member_matches {
+                ctx.insert("memberMatches", Data::Groups(group));
+            }
+            renderer
+                .render_template(template, &ctx)
+                .map_err(render_failed)?
+        }
+        Transform::Vec(_) => {
+            let mut matched = rules
+                .iter()
+                .filter(|rule| !wildcard_only || rule.wildcard)
+                .filter_map(|rule| Some((rule, rule.regex.captures(member)?)));
+            let matched = match config.match_strategy {
+                MatchStrategy::First => matched.next(),
+                MatchStrategy::LongestMatch => matched.reduce(|best, next| {
+                    if next.0.specificity > best.0.specificity {
+                        next
+                    } else {
+                        best
+                    }
+                }),
+            };
+
+            let mut result: Option<String> = None;
+            if let Some((rule, group)) = matched {
+                // Create a clone of the context, as we need to insert the
+                // `memberMatches` key for the chosen rule.
+                let mut ctx_with_member_matches: HashMap<&str, Data> = ctx.clone();
+
+                let group = Groups::new(&rule.regex, &group);
+                ctx_with_member_matches.insert("memberMatches", Data::Groups(&group));
+
+                result = Some(
+                    renderer
+                        .render_template(&rule.template, &ctx_with_member_matches)
+                        .map_err(render_failed)?,
+                );
+            }
+
+            match (result, &config.fallback) {
+                (Some(result), _) => result,
+                (None, Some(fallback)) if !wildcard_only => {
+                    if let Some(group) = &member_matches {
+                        ctx.insert("memberMatches", Data::Groups(group));
+                    }
+                    renderer
+                        .render_template(fallback, &ctx)
+                        .map_err(render_failed)?
+                }
+                (None, _) => return Ok(None),
+            }
+        }
+    };
+
+    // Every line is a separate path, finished on its own.
+    let new_paths = new_path
+        .split('\n')
+        .filter(|path| !path.is_empty())
+        .map(|path| finish_path(config, path, normalize_slashes))
+        .collect::<Vec<_>>();
+    Ok(Some(new_paths.join("\n")))
+}
+
+/// The number of literal characters any match of `hir` contains. Optional
+/// parts count for nothing, and an alternation for its shortest branch.
+fn literal_chars(hir: &Hir) -> usize {
+    match hir.kind() {
+        HirKind::Literal(literal) => std::str::from_utf8(&literal.0)
+            .map_or(literal.0.len(), |literal| literal.chars().count()),
+        HirKind::Repetition(repetition) => literal_chars(&repetition.sub) * repetition.min as usize,
+        HirKind::Capture(capture) => literal_chars(&capture.sub),
+        HirKind::Concat(hirs) => hirs.iter().map(literal_chars).sum(),
+        HirKind::Alternation(hirs) => hirs.iter().map(literal_chars).min().unwrap_or(0),
+        HirKind::Empty | HirKind::Class(_) | HirKind::Look(_) => 0,
+    }
+}
+
+/// Applies `prefix`, `suffix` and `extension` to a rendered path and, with
+/// `normalize_slashes`, collapses duplicate slashes.
+fn finish_path(config: &PackageConfig, path: &str, normalize_slashes: bool) -> String {
+    let mut new_path = format!(
+        "{}{}{}",
+        config.prefix.as_deref().unwrap_or_default(),
+        path,
+        config.suffix.as_deref().unwrap_or_default()
+    );
+    if let Some(extension) = &config.extension {
+        if !new_path.ends_with(extension.as_str()) {
+            new_path.push_str(extension);
+        }
+    }
+    if !normalize_slashes {
+        return new_path;
+    }
+    // Only allocate again when there were slashes to collapse.
+    let collapsed =
+        match DUP_SLASH_REGEX.replace_all(&new_path, |caps: &Captures| format!("{}/", &caps[1])) {
+            Cow::Owned(collapsed) => Some(collapsed),
+            Cow::Borrowed(_) => None,
+        };
+    collapsed.unwrap_or(new_path)
+}
+
+/// Re-exports `spec` alone from the original source of `old_decl`.
+fn keep_specifier(old_decl: &NamedExport, spec: &ExportSpecifier) -> NamedExport {
+    NamedExport {
+        span: old_decl.span,
+        specifiers: vec![spec.clone()],
+        src: old_decl.src.clone(),
+        type_only: false,
+        with: old_decl.with.clone(),
+    }
+}
+
+/// Every identifier name in a module, used to pick import bindings that do
+/// not shadow anything.
+#[derive(Default)]
+struct Bindings(HashSet<String>);
+
+impl Visit for Bindings {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.0.insert(ident.sym.to_string());
+    }
+}
+
+impl Bindings {
+    fn collect(module: &Module) -> Self {
+        let mut bindings = Bindings::default();
+        module.visit_with(&mut bindings);
+        bindings
+    }
+
+    /// Reserves an unused identifier derived from `name`.
+    fn fresh(&mut self, name: &str) -> Ident {
+        let base: String = std::iter::once('_')
+            .chain(name.chars().map(|c| {
+                if c.is_alphanumeric() || c == '_' || c == '$' {
+                    c
+                } else {
+                    '_'
+                }
+            }))
+            .collect();
+        let mut candidate = base.clone();
+        let mut n = 1;
+        while self.0.contains(&candidate) {
+            candidate = format!("{}{}", base, n);
+            n += 1;
+        }
+        self.0.insert(candidate.clone());
+        Ident::new(candidate.into(), DUMMY_SP)
+    }
+}
+
+/// Splits `export { a as b } from 'src'` into `import { a as _a } from 'src'`
+/// and `export { _a as b }`. Declarations without a source or with specifiers
+/// that cannot be imported, such as type-only ones, are returned unchanged.
+fn split_import_export(decl: NamedExport, bindings: &mut Bindings) -> Vec<ModuleItem> {
+    let splittable = decl.src.is_some()
+        && !decl.type_only
+        && decl.specifiers.iter().all(|spec| match spec {
+            ExportSpecifier::Named(named_spec) => !named_spec.is_type_only,
+            ExportSpecifier::Namespace(_) => true,
+            ExportSpecifier::Default(_) => false,
+        });
+    let src = match decl.src {
+        Some(src) if splittable => src,
+        src => {
+            return vec![ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                NamedExport { src, ..decl },
+            ))]
+        }
+    };
+
+    let mut imports = Vec::with_capacity(decl.specifiers.len());
+    let mut exports = Vec::with_capacity(decl.specifiers.len());
+    for spec in decl.specifiers {
+        let (import, local, exported) = match spec {
+            ExportSpecifier::Named(named_spec) => {
+                let local = bindings.fresh(export_name_str(&named_spec.orig));
+                let import = ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: named_spec.span,
+                    local: local.clone(),
+                    imported: Some(named_spec.orig.clone()),
+                    is_type_only: false,
+                });
+                let exported = named_spec.exported.unwrap_or(named_spec.orig);
+                (import, local, exported)
+            }
+            ExportSpecifier::Namespace(namespace_spec) => {
+                let local = bindings.fresh(export_name_str(&namespace_spec.name));
+                let import = ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                    span: namespace_spec.span,
+                    local: local.clone(),
+                });
+                (import, local, namespace_spec.name)
+            }
+            ExportSpecifier::Default(_) => unreachable!("checked above"),
+        };
+        imports.push(import);
+        exports.push(ExportSpecifier::Named(ExportNamedSpecifier {
+            span: DUMMY_SP,
+            orig: ModuleExportName::Ident(local),
+            exported: Some(exported),
+            is_type_only: false,
+        }));
+    }
+
+    vec![
+        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+            span: decl.span,
+            specifiers: imports,
+            src,
+            type_only: false,
+            with: decl.with,
+        })),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+            span: decl.span,
+            specifiers: exports,
+            src: None,
+            type_only: false,
+            with: None,
+        })),
+    ]
+}
+
+/// Merges exports of named specifiers that share a source into the first of
+/// them, keeping everything else in place.
+fn merge_same_source(decls: Vec<NamedExport>) -> Vec<NamedExport> {
+    let src_value = |decl: &NamedExport| decl.src.as_ref().map(|src| src.value.clone());
+    let mergeable = |decl: &NamedExport| {
+        decl.specifiers
+            .iter()
+            .all(|spec| matches!(spec, ExportSpecifier::Named(_)))
+    };
+    let mut out: Vec<NamedExport> = Vec::with_capacity(decls.len());
+    for decl in decls {
+        if mergeable(&decl) {
+            if let Some(target) = out
+                .iter_mut()
+                .find(|x| src_value(x) == src_value(&decl) && x.with == decl.with && mergeable(x))
+            {
+                target.specifiers.extend(decl.specifiers);
+                continue;
+            }
+        }
+        out.push(decl);
+    }
+    out
+}
+
+impl<'a> Rewriter<'a> {
+    fn render_path(&self, member: &Member) -> Result<Option<String>, TransformError> {
+        render_path(
+            self.renderer,
+            self.config,
+            self.compiled,
+            self.key,
+            member,
+            &self.group,
+            self.normalize_slashes,
+        )
+    }
+
+    /// Whether `member` is left out of the rewrite by `exclude` or `include`.
+    fn is_excluded(&self, member: &str) -> bool {
+        let included = match &self.compiled.include {
+            Some(include) => include.iter().any(|pattern| pattern.is_match(member)),
+            None => true,
+        };
+        !included
+            || self
+                .compiled
+                .exclude
+                .iter()
+                .any(|pattern| pattern.is_match(member))
+    }
+
+    /// Applies the `on_missing` policy for a member without a transform.
+    /// Returns `Ok` when the original export should be kept.
+    fn handle_missing(&self, span: Span, member: &str) -> Result<(), TransformError> {
+        let err = TransformError::MissingTransform {
+            package: self.key.to_string(),
+            member: member.to_string(),
+        };
+        match self.config.on_missing {
+            MissingBehavior::Panic => return Err(err),
+            MissingBehavior::Warn => report_warning(span, &err),
+            MissingBehavior::Keep => {}
+        }
+        Ok(())
+    }
+
+    /// Matches the exported name of `spec` against the package pattern,
+    /// returning a rewriter with its captures as `matches`.
+    fn match_exported_name<'b>(&'b self, spec: &'b ExportSpecifier) -> Option<Rewriter<'b>> {
+        let group = self.regex.captures(exported_name_str(spec))?;
+        Some(Rewriter {
+            group: Groups::new(self.regex, &group),
+            ..*self
+        })
+    }
+
+    fn rewrite_named(&self, old_decl: &NamedExport) -> Result<Vec<NamedExport>, TransformError> {
+        // Every generated export reuses the span of the original one, so its leading
+        // comments stay attached. The printer takes comments when it emits them, so
+        // they only end up on the first export.
+        let mut out: Vec<NamedExport> = Vec::with_capacity(old_decl.specifiers.len());
+
+        // `export {} from 'pkg'` still evaluates the package, so it is kept rather
+        // than rewritten to nothing.
+        if old_decl.specifiers.is_empty() {
+            out.push(old_decl.clone());
+            return Ok(out);
+        }
+
+        let attributes = import_attributes(old_decl.with.as_deref());
+        let total = old_decl.specifiers.len();
+        for (index, spec) in old_decl.specifiers.iter().enumerate() {
+            // A package matching on exported names only claims the specifiers it
+            // matches. The others go to the next package that claims them, as if
+            // they were exported on their own.
+            let by_name;
+            let this = match self.config.match_on {
+                MatchTarget::Source => self,
+                MatchTarget::ExportedName => match self.match_exported_name(spec).or_else(|| {
+                    self.folder.find_package_from(
+                        Some(self.key),
+                        &[exported_name_str(spec)],
+                        self.index + 1,
+                    )
+                }) {
+                    Some(rewriter) => {
+                        by_name = rewriter;
+                        &by_name
+                    }
+                    None => {
+                        out.push(keep_specifier(old_decl, spec));
+                        continue;
+                    }
+                },
+            };
+            match spec {
+                // Type-only specifiers have no runtime binding to rewrite, so they are
+                // kept as is, `type` keyword included.
+                ExportSpecifier::Named(named_spec) if named_spec.is_type_only => {
+                    out.push(keep_specifier(old_decl, spec));
+                }
+                // Excluded and non-included members are kept before anything else looks at them, so they
+                // never hit `prevent_full_export` either.
+                ExportSpecifier::Named(ExportNamedSpecifier { orig, .. })
+                    if this.is_excluded(export_name_str(orig)) =>
+                {
+                    out.push(keep_specifier(old_decl, spec));
+                }
+                ExportSpecifier::Namespace(ExportNamespaceSpecifier { name, .. })
+                    if this.is_excluded(export_name_str(name)) =>
+                {
+                    out.push(keep_specifier(old_decl, spec));
+                }
+                ExportSpecifier::Default(ExportDefaultSpecifier { exported })
+                    if this.is_excluded(&exported.sym) =>
+                {
+                    out.push(keep_specifier(old_decl, spec));
+                }
+                ExportSpecifier::Named(named_spec) => {
+                    let name_str = export_name_str(&named_spec.orig);
+
+                    let member = Member {
+                        name: name_str,
+                        exported: export_name_str(
+                            named_spec.exported.as_ref().unwrap_or(&named_spec.orig),
+                        ),
+                        all: false,
+                        position: Some((index, total)),
+                        with: &attributes,
+                    };
+
+                    let new_path = match this.render_path(&member)? {
+                        Some(new_path) => new_path,
+                        None => {
+                            this.handle_missing(old_decl.span, name_str)?;
+                            out.push(keep_specifier(old_decl, spec));
+                            continue;
+                        }
+                    };
+
+                    // Converting `default` to a namespace would lose its default semantics,
+                    // so it is always re-exported by name.
+                    let specifier = if this.config.skip_default_conversion || name_str == "default"
+                    {
+                        ExportSpecifier::Named(named_spec.clone())
+                    } else if member.exported == "default" {
+                        // `export { Button as default }` re-exports the default export of
+                        // the member's own module.
+                        ExportSpecifier::Named(ExportNamedSpecifier {
+                            span: named_spec.span,
+                            orig: ModuleExportName::Ident(Ident::new("default".into(), DUMMY_SP)),
+                            exported: None,
+                            is_type_only: false,
+                        })
+                    } else {
+                        // The name is reused as is, so a string-literal name such as
+                        // `"foo bar"` is never coerced to an invalid identifier.
+                        ExportSpecifier::Namespace(ExportNamespaceSpecifier {
+                            span: named_spec.span,
+                            name: named_spec
+                                .exported
+                                .clone()
+                                .unwrap_or(named_spec.orig.clone()),
+                        })
+                    };
+                    let new_path = this.single_path(member.exported, new_path)?;
+                    out.push(NamedExport {
+                        span: old_decl.span,
+                        specifiers: vec![specifier],
+                        src: Some(Box::new(this.quote_style.str(&new_path))),
+                        type_only: false,
+                        with: old_decl.with.clone(),
+                    });
+                }
+                ExportSpecifier::Namespace(namespace_spec) => {
+                    // `export * as ns from 'pkg'` uses the namespace name as the member.
+                    let name_str = export_name_str(&namespace_spec.name);
+
+                    let member = Member {
+                        name: name_str,
+                        exported: name_str,
+                        all: false,
+                        position: Some((index, total)),
+                        with: &attributes,
+                    };
+
+                    let src = match this.render_path(&member)? {
+                        Some(new_path) => {
+                            let new_path = this.single_path(name_str, new_path)?;
+                            Some(Box::new(this.quote_style.str(&new_path)))
+                        }
+                        None => {
+                            this.handle_missing(old_decl.span, name_str)?;
+                            old_decl.src.clone()
+                        }
+                    };
+                    out.push(NamedExport {
+                        span: old_decl.span,
+                        specifiers: vec![spec.clone()],
+                        src,
+                        type_only: false,
+                        with: old_decl.with.clone(),
+                    });
+                }
+                _ => {
+                    if this.config.prevent_full_export {
+                        return Err(TransformError::FullExport {
+                            package: this.key.to_string(),
+                            specifier: exported_name_str(spec).to_string(),
+                        });
+                    } else {
+                        // Give up on this specifier only, keeping it in its own
+                        // passthrough export alongside the ones rewritten so far.
+                        out.push(keep_specifier(old_decl, spec));
+                    }
+                }
+            }
+        }
+
+        if self.config.merge_same_source && self.config.skip_default_conversion {
+            out = merge_same_source(out);
+        }
+
+        Ok(out)
+    }
+
+    /// Checks that `new_path` is a single path, as every path would export
+    /// `member` again.
+    fn single_path(&self, member: &str, new_path: String) -> Result<String, TransformError> {
+        match new_path.split('\n').count() {
+            1 => Ok(new_path),
+            paths => Err(TransformError::DuplicateExport {
+                package: self.key.to_string(),
+                member: member.to_string(),
+                paths,
+            }),
+        }
+    }
+
+    fn rewrite_all(&self, old_decl: &ExportAll) -> Result<Vec<ExportAll>, TransformError> {
+        let mut out: Vec<ExportAll> = vec![];
+
+        let attributes = import_attributes(old_decl.with.as_deref());
+        let member = Member {
+            name: "*",
+            exported: "*",
+            all: true,
+            position: None,
+            with: &attributes,
+        };
+
+        let new_path = match self.render_path(&member)? {
+            Some(new_path) => new_path,
+            None => {
+                self.handle_missing(old_decl.span, "*")?;
+                return Ok(vec![old_decl.clone()]);
+            }
+        };
+
+        for new_path in new_path.split('\n') {
+            out.push(ExportAll {
+                span: old_decl.span,
+                src: Box::new(self.quote_style.str(new_path)),
+                type_only: false,
+                with: old_decl.with.clone(),
+            });
+        }
+        Ok(out)
+    }
+}
+
+impl FoldExports {
+    fn should_rewrite<'a>(&'a self, name: Option<&'a str>) -> Option<Rewriter<'a>> {
+        self.find_package(name, &[])
+    }
+
+    /// Like [FoldExports::should_rewrite], also trying the packages matching on
+    /// the exported names of `decl`.
+    fn should_rewrite_named<'a>(&'a self, decl: &'a NamedExport) -> Option<Rewriter<'a>> {
+        if decl.type_only {
+            return None;
+        }
+        let names: Vec<&str> = decl
+            .specifiers
+            .iter()
+            .filter(|spec| !matches!(spec, ExportSpecifier::Named(x) if x.is_type_only))
+            .map(exported_name_str)
+            .collect();
+        self.find_package(decl.src.as_ref().map(|x| &*x.value), &names)
+    }
+
+    /// Finds the first package whose pattern matches the source `name`, or one
+    /// of the `exported` names for packages matching on them.
+    fn find_package<'a>(
+        &'a self,
+        name: Option<&'a str>,
+        exported: &[&'a str],
+    ) -> Option<Rewriter<'a>> {
+        self.find_package_from(name, exported, 0)
+    }
+
+    /// Like [FoldExports::find_package], skipping the packages before `start`.
+    fn find_package_from<'a>(
+        &'a self,
+        name: Option<&'a str>,
+        exported: &[&'a str],
+        start: usize,
+    ) -> Option<Rewriter<'a>> {
+        match name {
+            // An empty source can only come from a malformed AST, and must not match
+            // patterns that happen to accept the empty string.
+            None | Some("") => None,
+            Some(name) => {
+                for (index, package) in self.packages.iter().enumerate().skip(start) {
+                    let group = match package.config.match_on {
+                        MatchTarget::Source => package.regex.captures(name).map(|group| {
+                            // Prefer the captures of the source without its extension, as
+                            // long as the stripped source still matches the package pattern.
+                            package
+                                .config
+                                .strip_extensions
+                                .iter()
+                                .find_map(|ext| name.strip_suffix(ext.as_str()))
+                                .and_then(|stripped| package.regex.captures(stripped))
+                                .unwrap_or(group)
+                        }),
+                        MatchTarget::ExportedName => exported
+                            .iter()
+                            .find_map(|exported| package.regex.captures(exported)),
+                    };
+                    if let Some(group) = group {
+                        let group = Groups::new(&package.regex, &group);
+                        return Some(Rewriter {
+                            folder: self,
+                            index,
+                            renderer: &self.renderer,
+                            key: name,
+                            regex: &package.regex,
+                            config: &package.config,
+                            compiled: &package.compiled,
+                            group,
+                            quote_style: self.quote_style,
+                            normalize_slashes: self.normalize_slashes,
+                        });
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Rewrites again every export of `decls` whose source changed from `src`
+    /// with the first matching package, when chaining is enabled. `applied`
+    /// lists the packages the export already went through.
+    fn chain_named(
+        &self,
+        src: Option<&str>,
+        decls: Vec<NamedExport>,
+        applied: &[usize],
+    ) -> Result<Vec<NamedExport>, TransformError> {
+        if !self.chain {
+            return Ok(decls);
+        }
+        let mut chained = Vec::with_capacity(decls.len());
+        for decl in decls {
+            let next = decl.src.as_ref().map(|x| x.value.clone());
+            let next = next.as_deref();
+            if next != src {
+                // Only the source changed, so packages matching on exported
+                // names would just pick the rewritten export up again.
+                if let Some(rewriter) = self.should_rewrite(next) {
+                    self.check_depth(&rewriter, applied)?;
+                    let rewritten = rewriter.rewrite_named(&decl)?;
+                    let applied = [applied, &[rewriter.index]].concat();
+                    chained.extend(self.chain_named(next, rewritten, &applied)?);
+                    continue;
+                }
+            }
+            chained.push(decl);
+        }
+        Ok(chained)
+    }
+
+    /// Fails if the package of `rewriter` was `applied` `max_depth` times
+    /// already, which would otherwise keep expanding its own output.
+    fn check_depth(&self, rewriter: &Rewriter, applied: &[usize]) -> Result<(), TransformError> {
+        let depth = applied.iter().filter(|&&i| i == rewriter.index).count();
+        if depth >= self.max_depth {
+            return Err(TransformError::MaxDepthExceeded {
+                package: self.packages[rewriter.index].key.clone(),
+                source: rewriter.key.to_string(),
+                max_depth: self.max_depth,
+            });
+        }
+        Ok(())
+    }
+
+    /// Like [FoldExports::chain_named], for `export * from` declarations.
+    fn chain_all(
+        &self,
+        src: Option<&str>,
+        decls: Vec<ExportAll>,
+        applied: &[usize],
+    ) -> Result<Vec<ExportAll>, TransformError> {
+        if !self.chain {
+            return Ok(decls);
+        }
+        let mut chained = Vec::with_capacity(decls.len());
+        for decl in decls {
+            let next = decl.src.value.clone();
+            if Some(&*next) != src {
+                if let Some(rewriter) = self.should_rewrite(Some(&next)) {
+                    self.check_depth(&rewriter, applied)?;
+                    let rewritten = rewriter.rewrite_all(&decl)?;
+                    let applied = [applied, &[rewriter.index]].concat();
+                    chained.extend(self.chain_all(Some(&next), rewritten, &applied)?);
+                    continue;
+                }
+            }
+            chained.push(decl);
+        }
+        Ok(chained)
+    }
+}
+
+impl FoldExports {
+    /// Turns `export * from` into a named re-export of the known members of
+    /// the matching package, if it lists them.
+    fn expand_known_members(&self, item: &ModuleItem) -> Option<ModuleItem> {
+        let decl = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)) if !decl.type_only => decl,
+            _ => return None,
+        };
+        let members = self
+            .should_rewrite(Some(&decl.src.value))
+            .and_then(|rewriter| rewriter.config.known_members.as_ref())?;
+        let specifiers = members
+            .iter()
+            .map(|member| {
+                let orig = match Ident::verify_symbol(member) {
+                    Ok(()) => ModuleExportName::Ident(Ident::new(member.as_str().into(), DUMMY_SP)),
+                    Err(_) => ModuleExportName::Str(Str::from(member.as_str())),
+                };
+                ExportSpecifier::Named(ExportNamedSpecifier {
+                    span: DUMMY_SP,
+                    orig,
+                    exported: None,
+                    is_type_only: false,
+                })
+            })
+            .collect();
+        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+            NamedExport {
+                span: decl.span,
+                specifiers,
+                src: Some(decl.src.clone()),
+                type_only: false,
+                with: decl.with.clone(),
+            },
+        )))
+    }
+}
+
+impl Fold for FoldExports {
+    noop_fold_type!();
+
+    fn fold_module(&mut self, mut module: Module) -> Module {
+        let mut new_items: Vec<ModuleItem> = vec![];
+        let mut bindings = if self
+            .packages
+            .iter()
+            .any(|package| package.config.emit_import_export)
+        {
+            Bindings::collect(&module)
+        } else {
+            Bindings::default()
+        };
+        for item in module.body {
+            // An expanded `export *` is rewritten as a named export, but it is
+            // the original statement that is previewed, kept on error and
+            // compared against to tell whether anything changed.
+            let (item, original) = match self.expand_known_members(&item) {
+                Some(expanded) => (expanded, Some(item)),
+                None => (item, None),
+            };
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) => {
+                    // Type-only exports are kept as is, without going through the
+                    // rewriter.
+                    let src = match decl.src {
+                        Some(ref x) if !decl.type_only => Some(&*x.value),
+                        _ => None,
+                    };
+                    match self.should_rewrite_named(&decl) {
+                        Some(rewriter) => {
+                            match rewriter
+                                .rewrite_named(&decl)
+                                .and_then(|rewritten| {
+                                    self.chain_named(src, rewritten, &[rewriter.index])
+                                })
+                                .map(|rewritten| {
+                                    self.sort_rewritten(rewritten, |x| {
+                                        x.src.as_ref().map_or("", |x| &*x.value)
+                                    })
+                                }) {
+                                Ok(rewritten) if self.preview.is_some() => {
+                                    let preview = self.preview.as_ref().unwrap();
+                                    for x in &rewritten {
+                                        let new_src = x.src.as_ref().map_or("", |x| &*x.value);
+                                        for spec in &x.specifiers {
+                                            preview.record(
+                                                src.unwrap(),
+                                                exported_name_str(spec),
+                                                new_src,
+                                            );
+                                        }
+                                    }
+                                    new_items.push(original.unwrap_or(ModuleItem::ModuleDecl(
+                                        ModuleDecl::ExportNamed(decl),
+                                    )))
+                                }
+                                // Nothing of the expanded members moved, so the
+                                // `export *` stays rather than narrowing it.
+                                Ok(rewritten)
+                                    if original.is_some()
+                                        && rewritten
+                                            .iter()
+                                            .all(|x| x.src.as_ref().map(|x| &*x.value) == src) =>
+                                {
+                                    new_items.push(original.unwrap())
+                                }
+                                Ok(rewritten) if rewriter.config.emit_import_export => {
+                                    self.count_named(rewriter.index, src, &rewritten);
+                                    let items: Vec<_> = rewritten
+                                        .into_iter()
+                                        .flat_map(|x| split_import_export(x, &mut bindings))
+                                        .collect();
+                                    self.dirty.track(
+                                        &original.unwrap_or(ModuleItem::ModuleDecl(
+                                            ModuleDecl::ExportNamed(decl),
+                                        )),
+                                        &items,
+                                    );
+                                    new_items.extend(items);
+                                }
+                                Ok(rewritten) => {
+                                    self.count_named(rewriter.index, src, &rewritten);
+                                    let items: Vec<_> = rewritten
+                                        .into_iter()
+                                        .map(|x| ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(x)))
+                                        .collect();
+                                    self.dirty.track(
+                                        &original.unwrap_or(ModuleItem::ModuleDecl(
+                                            ModuleDecl::ExportNamed(decl),
+                                        )),
+                                        &items,
+                                    );
+                                    new_items.extend(items);
+                                }
+                                Err(err) => {
+                                    report_error(&self.errors, decl.span, err);
+                                    new_items.push(original.unwrap_or(ModuleItem::ModuleDecl(
+                                        ModuleDecl::ExportNamed(decl),
+                                    )))
+                                }
+                            }
+                        }
+                        None => new_items.push(
+                            original
+                                .unwrap_or(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl))),
+                        ),
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)) => {
+                    let src = if !decl.type_only {
+                        Some(&*decl.src.value)
+                    } else {
+                        None
+                    };
+                    match self.should_rewrite(src) {
+                        Some(rewriter) => match rewriter
+                            .rewrite_all(&decl)
+                            .and_then(|rewritten| self.chain_all(src, rewritten, &[rewriter.index]))
+                            .map(|rewritten| self.sort_rewritten(rewritten, |x| &x.src.value))
+                        {
+                            Ok(rewritten) if self.preview.is_some() => {
+                                let preview = self.preview.as_ref().unwrap();
+                                for x in &rewritten {
+                                    preview.record(&decl.src.value, "*", &x.src.value);
+                                }
+                                new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)))
+                            }
+                            Ok(rewritten) => {
+                                if let Some(stats) = &self.stats {
+                                    let count = rewritten
+                                        .iter()
+                                        .filter(|x| x.src.value != decl.src.value)
+                                        .count();
+                                    stats.add(&self.packages[rewriter.index].key, count);
+                                }
+                                self.dirty.track(&decl, &rewritten);
+                                new_items.extend(
+                                    rewritten
+                                        .into_iter()
+                                        .map(|x| ModuleItem::ModuleDecl(ModuleDecl::ExportAll(x))),
+                                );
+                            }
+                            Err(err) => {
+                                report_error(&self.errors, decl.span, err);
+                                new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)))
+                            }
+                        },
+                        None => new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl))),
+                    }
+                }
+                // Local declarations have no source to match against a package, so they
+                // are never rewritten, even when `prevent_full_export` is set.
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)) => {
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)));
+                }
+                // Default exports have no source either; a default expression naming
+                // an import is left for the import transform to handle.
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(decl)) => {
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(decl)));
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(decl)) => {
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(decl)));
+                }
+                x => {
+                    new_items.push(x);
+                }
+            }
+        }
+        module.body = new_items;
+        module
+    }
+}
+
+impl FoldExports {
+    /// Compiles every package key and `Transform::Vec` pattern of `config`,
+    /// returning the first invalid one as an error.
+    pub fn new(config: Config) -> Result<Self, TransformError> {
+        Ok(CompiledConfig::new(config)?.folder())
+    }
+
+    /// Returns the keys of the packages matching the source `specifier`, in
+    /// the order they are tried. The first one is the package that rewrites
+    /// exports of the specifier, unless a package matching on exported names
+    /// comes before it and claims some of them. Those packages are not listed,
+    /// as they do not match on the source.
+    pub fn packages_matching(&self, specifier: &str) -> Vec<&str> {
+        if specifier.is_empty() {
+            return vec![];
+        }
+        self.packages
+            .iter()
+            .filter(|package| package.config.match_on == MatchTarget::Source)
+            .filter(|package| package.regex.is_match(specifier))
+            .map(|package| package.key.as_str())
+            .collect()
+    }
+
+    /// Returns a handle telling whether this folder rewrote any export.
+    pub fn dirty(&self) -> DirtyHandle {
+        self.dirty.clone()
+    }
+
+    /// Returns a handle collecting the errors of the exports this folder
+    /// kept as is. They are also reported through the swc diagnostics
+    /// handler, when one is installed.
+    pub fn errors(&self) -> ErrorsHandle {
+        self.errors.clone()
+    }
+
+    /// Stably sorts the exports rewritten from one statement by source, if
+    /// `sort_output` is set.
+    fn sort_rewritten<T>(&self, mut rewritten: Vec<T>, src: impl Fn(&T) -> &str) -> Vec<T> {
+        if self.sort_output {
+            rewritten.sort_by(|a, b| src(a).cmp(src(b)));
+        }
+        rewritten
+    }
+
+    /// Adds the members of `rewritten` whose source differs from `src` to the
+    /// stats of the package at `index`, if stats are collected.
+    fn count_named(&self, index: usize, src: Option<&str>, rewritten: &[NamedExport]) {
+        if let Some(stats) = &self.stats {
+            let count = rewritten
+                .iter()
+                .filter(|x| x.src.as_ref().map(|x| &*x.value) != src)
+                .map(|x| x.specifiers.len())
+                .sum();
+            stats.add(&self.packages[index].key, count);
+        }
+    }
+
+    /// Registers a template helper in addition to the built-in ones,
+    /// replacing any helper already registered as `name`.
+    pub fn with_helper(mut self, name: &str, helper: Box<dyn HelperDef + Send + Sync>) -> Self {
+        Arc::make_mut(&mut self.renderer).register_helper(name, helper);
+        self
+    }
+}
+
+/// A config whose package keys and patterns are compiled, and whose template
+/// renderer is set up, once. Build tools can keep one around and create a
+/// cheap [FoldExports] per file with [CompiledConfig::folder].
+///
+/// Clones share the compiled packages and renderer.
+#[derive(Clone)]
+pub struct CompiledConfig {
+    renderer: Arc<handlebars::Handlebars<'static>>,
+    packages: Arc<Vec<Package>>,
+    quote_style: QuoteStyle,
+    chain: bool,
+    max_depth: usize,
+    normalize_slashes: bool,
+    sort_output: bool,
+}
+
+impl CompiledConfig {
+    /// Compiles every package key and `Transform::Vec` pattern of `config`,
+    /// returning the first invalid one as an error.
+    pub fn new(config: Config) -> Result<Self, TransformError> {
+        let mut packages = Vec::with_capacity(config.packages.len());
+        for (k, v) in config.packages {
+            packages.push(Package {
+                regex: compile_package_key(&k, &v)?,
+                compiled: CompiledTransform::new(&v)?,
+                key: k,
+                config: v,
+            });
+        }
+        let mut renderer = renderer();
+        renderer.set_strict_mode(config.on_missing_variable == MissingVariableBehavior::Error);
+        Ok(CompiledConfig {
+            renderer: Arc::new(renderer),
+            packages: Arc::new(packages),
+            quote_style: config.quote_style,
+            chain: config.chain,
+            max_depth: config.max_depth,
+            normalize_slashes: config.normalize_slashes,
+            sort_output: config.sort_output,
+        })
+    }
+
+    /// Creates a fold sharing this config. Each fold has its own dirty flag.
+    pub fn folder(&self) -> FoldExports {
+        FoldExports {
+            renderer: self.renderer.clone(),
+            packages: self.packages.clone(),
+            quote_style: self.quote_style,
+            chain: self.chain,
+            max_depth: self.max_depth,
+            dirty: DirtyHandle::default(),
+            errors: ErrorsHandle::default(),
+            preview: None,
+            stats: None,
+            normalize_slashes: self.normalize_slashes,
+            sort_output: self.sort_output,
+        }
+    }
+
+    /// Registers a template helper for every fold created afterwards, like
+    /// [FoldExports::with_helper].
+    pub fn with_helper(mut self, name: &str, helper: Box<dyn HelperDef + Send + Sync>) -> Self {
+        Arc::make_mut(&mut self.renderer).register_helper(name, helper);
+        self
+    }
+}
+
+/// Compiles `config` once for many files. See [CompiledConfig].
+pub fn compile(config: Config) -> Result<CompiledConfig, TransformError> {
+    CompiledConfig::new(config)
+}
+
+pub fn modularize_exports(config: Config) -> FoldExports {
+    try_modularize_exports(config).expect("transform-exports: invalid regex")
+}
+
+/// Like [modularize_exports], but leaves modules untouched and records the
+/// rewrites it would make into the returned sink instead, e.g. to audit a
+/// config before applying it.
+pub fn modularize_exports_preview(config: Config) -> (FoldExports, PreviewSink) {
+    let mut folder = modularize_exports(config);
+    let preview = PreviewSink::default();
+    folder.preview = Some(preview.clone());
+    (folder, preview)
+}
+
+/// Like [modularize_exports], also returning a handle that tells whether any
+/// export was rewritten, so that callers can skip unchanged files.
+pub fn modularize_exports_tracked(config: Config) -> (FoldExports, DirtyHandle) {
+    let folder = modularize_exports(config);
+    let dirty = folder.dirty();
+    (folder, dirty)
+}
+
+/// Like [modularize_exports], also returning a handle that counts the members
+/// rewritten by each package, e.g. to measure the impact of a migration.
+pub fn modularize_exports_with_stats(config: Config) -> (FoldExports, StatsHandle) {
+    let mut folder = modularize_exports(config);
+    let stats = StatsHandle::default();
+    folder.stats = Some(stats.clone());
+    (folder, stats)
+}
+
+/// Like [modularize_exports], but validates every package key and
+/// `Transform::Vec` pattern up front and reports the first invalid one
+/// instead of panicking.
+pub fn try_modularize_exports(config: Config) -> Result<FoldExports, TransformError> {
+    FoldExports::new(config)
+}
+
+/// Creates a template renderer with all built-in helpers registered.
+pub fn renderer() -> Handlebars<'static> {
+    let mut renderer = Handlebars::new();
+    // Templates render module specifiers, not HTML.
+    renderer.register_escape_fn(handlebars::no_escape);
+    // `eq`, `ne` and the other comparison helpers used with `#if`, as well as
+    // `lookup` for reading `data`, are built into handlebars, so only the
+    // string helpers are registered here.
+    renderer.register_helper("lowerCase", Box::new(helper_lower_case));
+    renderer.register_helper("upperCase", Box::new(helper_upper_case));
+    renderer.register_helper("camelCase", Box::new(helper_camel_case));
+    renderer.register_helper("kebabCase", Box::new(helper_kebab_case));
+    renderer.register_helper("snakeCase", Box::new(helper_snake_case));
+    renderer.register_helper("pascalCase", Box::new(helper_pascal_case));
+    renderer.register_helper("constantCase", Box::new(helper_constant_case));
+    renderer.register_helper("titleCase", Box::new(helper_title_case));
+    renderer.register_helper("sentenceCase", Box::new(helper_sentence_case));
+    renderer.register_helper("pathCase", Box::new(helper_path_case));
+    renderer.register_helper("reverseWords", Box::new(helper_reverse_words));
+    renderer.register_helper("dotCase", Box::new(helper_dot_case));
+    renderer.register_helper("upperFirst", Box::new(helper_upper_first));
+    renderer.register_helper("lowerFirst", Box::new(helper_lower_first));
+    renderer.register_helper("replace", Box::new(helper_replace));
+    renderer.register_helper("join", Box::new(helper_join));
+    renderer.register_helper("length", Box::new(LengthHelper));
+    renderer.register_helper("default", Box::new(helper_default));
+    renderer.register_helper("firstNonEmpty", Box::new(helper_first_non_empty));
+    renderer.register_helper("urlEncode", Box::new(helper_url_encode));
+    renderer.register_helper("trimPrefix", Box::new(helper_trim_prefix));
+    renderer.register_helper("trimSuffix", Box::new(helper_trim_suffix));
+    renderer.register_helper("env", Box::new(helper_env));
+    renderer.register_helper("startsWith", Box::new(PredicateHelper(helper_starts_with)));
+    renderer.register_helper("endsWith", Box::new(PredicateHelper(helper_ends_with)));
+    renderer
+}
+
+fn compile_package_key(key: &str, config: &PackageConfig) -> Result<CachedRegex, TransformError> {
+    match config.match_mode {
+        MatchMode::Regex if !config.anchored => compile_regex(key, key, config.case_insensitive),
+        MatchMode::Regex => compile_pattern(key, config.case_insensitive),
+        MatchMode::Glob => compile_regex(key, &glob_to_regex(key), config.case_insensitive),
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::with_capacity(glob.len() * 2);
+    regex.push('^');
+    let mut rest = glob;
+    while let Some(c) = rest.chars().next() {
+        if let Some(tail) = rest.strip_prefix("/**").filter(|tail| tail.is_empty()) {
+            regex.push_str("(?:/(.*))?");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("**") {
+            regex.push_str("(.*)");
+            rest = tail;
+        } else {
+            match c {
+                '*' => regex.push_str("([^/]*)"),
+                '?' => regex.push_str("([^/])"),
+                c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+fn compile_pattern(pattern: &str, case_insensitive: bool) -> Result<CachedRegex, TransformError> {
+    // XXX: Should we keep this hack? The pattern is anchored on both sides,
+    // whatever anchors it already has, so that a half-anchored pattern is
+    // fully anchored too; package keys can opt out with `anchored: false`.
+    // The group keeps a top-level `|` from escaping the anchors.
+    let mut inner = pattern.strip_prefix('^').unwrap_or(pattern);
+    if let Some(rest) = inner.strip_suffix('$') {
+        let escapes = rest.len() - rest.trim_end_matches('\\').len();
+        if escapes % 2 == 0 {
+            inner = rest;
+        }
+    }
+    compile_regex(pattern, &format!("^(?:{})$", inner), case_insensitive)
+}
+
+/// Compiles `regex`, which was built from `pattern` as written in the config.
+/// Errors report `pattern`, so that they point into what the user wrote
+/// rather than into the anchored form.
+fn compile_regex(
+    pattern: &str,
+    regex: &str,
+    case_insensitive: bool,
+) -> Result<CachedRegex, TransformError> {
+    // The flag goes in front of any anchors, which it does not affect.
+    let regex = if case_insensitive {
+        Cow::Owned(format!("(?i){}", regex))
+    } else {
+        Cow::Borrowed(regex)
+    };
+    CachedRegex::new(&regex).map_err(|err| TransformError::InvalidRegex {
+        pattern: pattern.to_string(),
+        source: match regex_syntax::parse(pattern) {
+            Err(syntax) => RegexError::new(syntax),
+            Ok(_) => err,
+        },
+    })
+}
+
+fn helper_lower_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+    out.write(param.to_lowercase().as_ref())?;
+    Ok(())
+}
+
+fn helper_upper_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+    out.write(param.to_uppercase().as_ref())?;
+    Ok(())
+}
+
+/// Converts `param` to `case`, splitting words at the `caseBoundaries` of the
+/// package being rendered, if any.
+fn to_case(ctx: &Context, param: &str, case: Case) -> String {
+    let boundaries = ctx
+        .data()
+        .get("caseBoundaries")
+        .and_then(|boundaries| Vec::<CaseBoundary>::deserialize(boundaries).ok());
+    match boundaries {
+        Some(boundaries) => {
+            let boundaries: Vec<Boundary> =
+                boundaries.into_iter().map(CaseBoundary::boundary).collect();
+            param.with_boundaries(&boundaries).to_case(case)
+        }
+        None => param.to_case(case),
+    }
+}
+
+fn helper_camel_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    out.write(to_case(ctx, param, Case::Camel).as_ref())?;
+    Ok(())
+}
+
+fn helper_kebab_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    out.write(to_case(ctx, param, Case::Kebab).as_ref())?;
+    Ok(())
+}
+
+fn helper_snake_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    out.write(to_case(ctx, param, Case::Snake).as_ref())?;
+    Ok(())
 }
 
-struct FoldExports {
-    renderer: handlebars::Handlebars<'static>,
-    packages: Vec<(CachedRegex, PackageConfig)>,
+fn helper_pascal_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    out.write(to_case(ctx, param, Case::Pascal).as_ref())?;
+    Ok(())
 }
 
-struct Rewriter<'a> {
-    renderer: &'a handlebars::Handlebars<'static>,
-    key: &'a str,
-    config: &'a PackageConfig,
-    group: Vec<&'a str>,
+fn helper_constant_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    out.write(to_case(ctx, param, Case::UpperSnake).as_ref())?;
+    Ok(())
 }
 
-impl<'a> Rewriter<'a> {
-    fn rewrite_named(&self, old_decl: &NamedExport) -> Vec<NamedExport> {
-        if old_decl.type_only || old_decl.with.is_some() {
-            return vec![old_decl.clone()];
-        }
+/// `myCoolButton` becomes `My Cool Button`. The spaces make this mostly useful
+/// outside of path segments.
+fn helper_title_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-        let mut out: Vec<NamedExport> = Vec::with_capacity(old_decl.specifiers.len());
+    out.write(to_case(ctx, param, Case::Title).as_ref())?;
+    Ok(())
+}
 
-        for spec in &old_decl.specifiers {
-            match spec {
-                ExportSpecifier::Named(named_spec) => {
-                    #[derive(Serialize)]
-                    #[serde(untagged)]
-                    enum Data<'a> {
-                        Plain(&'a str),
-                        Array(&'a [&'a str]),
-                    }
-                    let name_str = match &named_spec.orig {
-                        ModuleExportName::Ident(x) => x.as_ref(),
-                        ModuleExportName::Str(x) => x.value.as_ref(),
-                    };
+/// `myCoolButton` becomes `My cool button`. The spaces make this mostly useful
+/// outside of path segments.
+fn helper_sentence_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-                    let mut ctx: HashMap<&str, Data> = HashMap::new();
-                    ctx.insert("matches", Data::Array(&self.group[..]));
-                    ctx.insert("member", Data::Plain(name_str));
+    // convert_case has no sentence case, so capitalize the first lower case word.
+    let lower = to_case(ctx, param, Case::Lower);
+    let mut chars = lower.chars();
+    if let Some(first) = chars.next() {
+        out.write(&first.to_uppercase().collect::<String>())?;
+        out.write(chars.as_str())?;
+    }
+    Ok(())
+}
 
-                    let new_path = match &self.config.transform {
-                        Transform::String(s) => {
-                            self.renderer.render_template(s, &ctx).unwrap_or_else(|e| {
-                                panic!("error rendering template for '{}': {}", self.key, e);
-                            })
-                        }
-                        Transform::Vec(v) => {
-                            let mut result: Option<String> = None;
-
-                            // We iterate over the items to find the first match
-                            v.iter().any(|(k, val)| {
-                                let mut key = k.to_string();
-                                if !key.starts_with('^') && !key.ends_with('$') {
-                                    key = format!("^{}$", key);
-                                }
+/// `UserProfileSettings` becomes `user/profile/settings`.
+fn helper_path_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-                                // Create a clone of the context, as we need to insert the
-                                // `memberMatches` key for each key we try.
-                                let mut ctx_with_member_matches: HashMap<&str, Data> =
-                                    HashMap::new();
-                                ctx_with_member_matches
-                                    .insert("matches", Data::Array(&self.group[..]));
-                                ctx_with_member_matches.insert("member", Data::Plain(name_str));
+    out.write(to_case(ctx, param, Case::Lower).replace(' ', "/").as_ref())?;
+    Ok(())
+}
 
-                                let regex = CachedRegex::new(&key)
-                                    .expect("transform-exports: invalid regex");
-                                let group = regex.captures(name_str);
+/// `fooBarBaz` becomes `baz/bar/foo`: the words of `pathCase`, in reverse.
+fn helper_reverse_words(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-                                if let Some(group) = group {
-                                    let group = group
-                                        .iter()
-                                        .map(|x| x.map(|x| x.as_str()).unwrap_or_default())
-                                        .collect::<Vec<&str>>()
-                                        .clone();
-                                    ctx_with_member_matches
-                                        .insert("memberMatches", Data::Array(&group[..]));
-
-                                    result = Some(
-                                        self.renderer
-                                            .render_template(val, &ctx_with_member_matches)
-                                            .unwrap_or_else(|e| {
-                                                panic!(
-                                                    "error rendering template for '{}': {}",
-                                                    self.key, e
-                                                );
-                                            }),
-                                    );
+    let words = to_case(ctx, param, Case::Lower);
+    let words: Vec<&str> = words.split(' ').rev().collect();
+    out.write(&words.join("/"))?;
+    Ok(())
+}
 
-                                    true
-                                } else {
-                                    false
-                                }
-                            });
-
-                            if let Some(result) = result {
-                                result
-                            } else {
-                                panic!(
-                                    "missing transform for export '{}' of package '{}'",
-                                    match named_spec.orig {
-                                        ModuleExportName::Ident(ref x) => x.as_ref(),
-                                        ModuleExportName::Str(ref x) => x.value.as_ref(),
-                                    },
-                                    self.key
-                                );
-                            }
-                        }
-                    };
+/// `solidHome` becomes `solid.home`. Dots are left alone by the slash cleanup.
+fn helper_dot_case(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    ctx: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-                    let new_path = DUP_SLASH_REGEX.replace_all(&new_path, |_: &Captures| "/");
-                    let specifier = if self.config.skip_default_conversion {
-                        ExportSpecifier::Named(named_spec.clone())
-                    } else {
-                        ExportSpecifier::Namespace(ExportNamespaceSpecifier {
-                            span: named_spec.span,
-                            name: named_spec
-                                .exported
-                                .clone()
-                                .unwrap_or(named_spec.orig.clone()),
-                        })
-                    };
-                    out.push(NamedExport {
-                        span: old_decl.span,
-                        specifiers: vec![specifier],
-                        src: Some(Box::new(Str::from(new_path.as_ref()))),
-                        type_only: false,
-                        with: None,
-                    });
-                }
-                _ => {
-                    if self.config.prevent_full_export {
-                        panic!(
-                            "export {:?} causes the entire module to be exported",
-                            old_decl
-                        );
-                    } else {
-                        // Give up
-                        return vec![old_decl.clone()];
-                    }
-                }
-            }
-        }
-        out
-    }
+    out.write(to_case(ctx, param, Case::Lower).replace(' ', ".").as_ref())?;
+    Ok(())
+}
 
-    fn rewrite_all(&self, old_decl: &ExportAll) -> Vec<ExportAll> {
-        if old_decl.type_only || old_decl.with.is_some() {
-            return vec![old_decl.clone()];
-        }
+fn helper_upper_first(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-        let mut out: Vec<ExportAll> = Vec::with_capacity(1);
+    let mut chars = param.chars();
+    if let Some(first) = chars.next() {
+        out.write(&first.to_uppercase().collect::<String>())?;
+        out.write(chars.as_str())?;
+    }
+    Ok(())
+}
 
-        #[derive(Serialize)]
-        #[serde(untagged)]
-        enum Data<'a> {
-            Plain(&'a str),
-            Array(&'a [&'a str]),
-        }
+fn helper_lower_first(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-        let mut ctx: HashMap<&str, Data> = HashMap::new();
-        ctx.insert("matches", Data::Array(&self.group[..]));
-        ctx.insert("member", Data::Plain("*"));
+    let mut chars = param.chars();
+    if let Some(first) = chars.next() {
+        out.write(&first.to_lowercase().collect::<String>())?;
+        out.write(chars.as_str())?;
+    }
+    Ok(())
+}
 
-        let new_path = match &self.config.transform {
-            Transform::String(s) => self.renderer.render_template(s, &ctx).unwrap_or_else(|e| {
-                panic!("error rendering template for '{}': {}", self.key, e);
-            }),
-            Transform::Vec(v) => {
-                let mut result: Option<String> = None;
-
-                // We iterate over the items to find the first match
-                v.iter().any(|(k, val)| {
-                    let mut key = k.to_string();
-                    if !key.starts_with('^') && !key.ends_with('$') {
-                        key = format!("^{}$", key);
-                    }
+fn helper_replace(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameters from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+    let pattern = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
+    let replacement = h.param(2).and_then(|v| v.value().as_str()).unwrap_or("");
 
-                    // Create a clone of the context, as we need to insert the
-                    // `memberMatches` key for each key we try.
-                    let mut ctx_with_member_matches: HashMap<&str, Data> = HashMap::new();
-                    ctx_with_member_matches.insert("matches", Data::Array(&self.group[..]));
-                    ctx_with_member_matches.insert("member", Data::Plain("*"));
+    let regex = CachedRegex::new(pattern)
+        .map_err(|e| RenderError::new(format!("replace: invalid regex '{}': {}", pattern, e)))?;
 
-                    let regex = CachedRegex::new(&key).expect("transform-exports: invalid regex");
-                    let group = regex.captures("*");
+    out.write(regex.replace_all(param, replacement).as_ref())?;
+    Ok(())
+}
 
-                    if let Some(group) = group {
-                        let group = group
-                            .iter()
-                            .map(|x| x.map(|x| x.as_str()).unwrap_or_default())
-                            .collect::<Vec<&str>>()
-                            .clone();
-                        ctx_with_member_matches.insert("memberMatches", Data::Array(&group[..]));
-
-                        result = Some(
-                            self.renderer
-                                .render_template(val, &ctx_with_member_matches)
-                                .unwrap_or_else(|e| {
-                                    panic!("error rendering template for '{}': {}", self.key, e);
-                                }),
-                        );
-
-                        true
-                    } else {
-                        false
-                    }
-                });
+/// Joins the strings of an array, or of the positional captures of a
+/// `matches` object with named groups, skipping empty ones.
+fn helper_join(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameters from helper or throw an error
+    let param = h.param(0).map(|v| v.value());
+    let separator = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
 
-                if let Some(result) = result {
-                    result
-                } else {
-                    panic!("missing transform for export * of package '{}'", self.key);
-                }
-            }
-        };
+    let joined =
+        non_empty_items(param).ok_or_else(|| RenderError::new("join: expected an array"))?;
 
-        let new_path = DUP_SLASH_REGEX.replace_all(&new_path, |_: &Captures| "/");
-        out.push(ExportAll {
-            span: old_decl.span,
-            src: Box::new(Str::from(new_path.as_ref())),
-            type_only: false,
-            with: None,
-        });
-        out
-    }
+    out.write(&joined.join(separator))?;
+    Ok(())
 }
 
-impl FoldExports {
-    fn should_rewrite<'a>(&'a self, name: Option<&'a str>) -> Option<Rewriter<'a>> {
-        match name {
-            None => None,
-            Some(name) => {
-                for (regex, config) in &self.packages {
-                    let group = regex.captures(name);
-                    if let Some(group) = group {
-                        let group = group
-                            .iter()
-                            .map(|x| x.map(|x| x.as_str()).unwrap_or_default())
-                            .collect::<Vec<&str>>();
-                        return Some(Rewriter {
-                            renderer: &self.renderer,
-                            key: name,
-                            config,
-                            group,
-                        });
-                    }
-                }
-                None
-            }
+/// Returns the non-empty strings of an array, or of the positional captures
+/// of a `matches` object with named groups.
+fn non_empty_items(param: Option<&JsonValue>) -> Option<Vec<&str>> {
+    let items: Vec<&JsonValue> = match param? {
+        JsonValue::Array(items) => items.iter().collect(),
+        JsonValue::Object(map) => {
+            let mut positional: Vec<(usize, &JsonValue)> = map
+                .iter()
+                .filter_map(|(k, v)| k.parse().ok().map(|k| (k, v)))
+                .collect();
+            positional.sort_by_key(|(k, _)| *k);
+            positional.into_iter().map(|(_, v)| v).collect()
         }
+        _ => return None,
+    };
+    Some(
+        items
+            .into_iter()
+            .filter_map(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .collect(),
+    )
+}
+
+/// Returns the number of non-empty strings of an array, or of the positional
+/// captures of a `matches` object, for use in a subexpression such as
+/// `{{#if (eq (length matches) 2)}}`. Groups that did not participate in the
+/// match are empty, so they are not counted.
+struct LengthHelper;
+
+impl HelperDef for LengthHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        let items = non_empty_items(h.param(0).map(|v| v.value()))
+            .ok_or_else(|| RenderError::new("length: expected an array"))?;
+        Ok(ScopedJson::Derived(JsonValue::from(items.len())))
     }
 }
 
-impl Fold for FoldExports {
-    noop_fold_type!();
+/// Percent-encodes every byte of its param outside the unreserved characters
+/// of RFC 3986, slashes included, so an encoded member stays a single path
+/// segment that the slash cleanup never touches.
+fn helper_url_encode(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    // get parameter from helper or throw an error
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-    fn fold_module(&mut self, mut module: Module) -> Module {
-        let mut new_items: Vec<ModuleItem> = vec![];
-        for item in module.body {
-            match item {
-                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) => {
-                    match self.should_rewrite(match decl.src {
-                        None => None,
-                        Some(ref x) => Some(&x.value),
-                    }) {
-                        Some(rewriter) => {
-                            let rewritten = rewriter.rewrite_named(&decl);
-                            new_items.extend(
-                                rewritten
-                                    .into_iter()
-                                    .map(|x| ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(x))),
-                            );
-                        }
-                        None => {
-                            new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)))
-                        }
-                    }
-                }
-                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)) => {
-                    match self.should_rewrite(Some(&decl.src.value)) {
-                        Some(rewriter) => {
-                            let rewritten = rewriter.rewrite_all(&decl);
-                            new_items.extend(
-                                rewritten
-                                    .into_iter()
-                                    .map(|x| ModuleItem::ModuleDecl(ModuleDecl::ExportAll(x))),
-                            );
-                        }
-                        None => new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl))),
-                    }
-                }
-                x => {
-                    new_items.push(x);
-                }
+    let mut encoded = String::with_capacity(param.len());
+    for byte in param.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
             }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
         }
-        module.body = new_items;
-        module
     }
+    out.write(&encoded)?;
+    Ok(())
 }
 
-pub fn modularize_exports(config: Config) -> impl Fold {
-    let mut folder = FoldExports {
-        renderer: handlebars::Handlebars::new(),
-        packages: vec![],
-    };
-    folder
-        .renderer
-        .register_helper("lowerCase", Box::new(helper_lower_case));
-    folder
-        .renderer
-        .register_helper("upperCase", Box::new(helper_upper_case));
-    folder
-        .renderer
-        .register_helper("camelCase", Box::new(helper_camel_case));
-    folder
-        .renderer
-        .register_helper("kebabCase", Box::new(helper_kebab_case));
-    for (mut k, v) in config.packages {
-        // XXX: Should we keep this hack?
-        if !k.starts_with('^') && !k.ends_with('$') {
-            k = format!("^{}$", k);
-        }
-        folder.packages.push((
-            CachedRegex::new(&k).expect("transform-exports: invalid regex"),
-            v,
-        ));
-    }
-    folder
+/// Emits the first non-empty string param, or nothing when there is none.
+fn helper_first_non_empty(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let first = h
+        .params()
+        .iter()
+        .filter_map(|v| v.value().as_str())
+        .find(|v| !v.is_empty())
+        .unwrap_or("");
+
+    out.write(first)?;
+    Ok(())
 }
 
-fn helper_lower_case(
+fn helper_default(
     h: &Helper<'_, '_>,
     _: &Handlebars<'_>,
     _: &Context,
     _: &mut RenderContext<'_, '_>,
     out: &mut dyn Output,
 ) -> HelperResult {
-    // get parameter from helper or throw an error
+    // get parameters from helper or throw an error
     let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
-    out.write(param.to_lowercase().as_ref())?;
+    let fallback = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    if param.is_empty() {
+        out.write(fallback)?;
+    } else {
+        out.write(param)?;
+    }
     Ok(())
 }
 
-fn helper_upper_case(
+fn helper_trim_prefix(
     h: &Helper<'_, '_>,
     _: &Handlebars<'_>,
     _: &Context,
     _: &mut RenderContext<'_, '_>,
     out: &mut dyn Output,
 ) -> HelperResult {
-    // get parameter from helper or throw an error
+    // get parameters from helper or throw an error
     let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
-    out.write(param.to_uppercase().as_ref())?;
+    let prefix = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
+
+    out.write(param.strip_prefix(prefix).unwrap_or(param))?;
     Ok(())
 }
 
-fn helper_camel_case(
+fn helper_trim_suffix(
     h: &Helper<'_, '_>,
     _: &Handlebars<'_>,
     _: &Context,
     _: &mut RenderContext<'_, '_>,
     out: &mut dyn Output,
 ) -> HelperResult {
-    // get parameter from helper or throw an error
+    // get parameters from helper or throw an error
     let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+    let suffix = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
 
-    out.write(param.to_case(Case::Camel).as_ref())?;
+    out.write(param.strip_suffix(suffix).unwrap_or(param))?;
     Ok(())
 }
 
-fn helper_kebab_case(
+/// A helper telling whether its two string params satisfy a predicate, meant
+/// as an `#if` subexpression such as `(startsWith member "use")`.
+struct PredicateHelper(fn(&str, &str) -> bool);
+
+impl HelperDef for PredicateHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+        let other = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
+        Ok(ScopedJson::Derived(JsonValue::Bool(self.0(param, other))))
+    }
+}
+
+fn helper_starts_with(param: &str, prefix: &str) -> bool {
+    param.starts_with(prefix)
+}
+
+fn helper_ends_with(param: &str, suffix: &str) -> bool {
+    param.ends_with(suffix)
+}
+
+/// Emits the value of an environment variable, or nothing when it is unset.
+/// The variable is read once, when the transform runs, not when the output
+/// does.
+fn helper_env(
     h: &Helper<'_, '_>,
     _: &Handlebars<'_>,
     _: &Context,
@@ -422,8 +2948,12 @@ fn helper_kebab_case(
     out: &mut dyn Output,
 ) -> HelperResult {
     // get parameter from helper or throw an error
-    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+    let name = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
 
-    out.write(param.to_case(Case::Kebab).as_ref())?;
+    let mut vars = ENV_VARS.lock().unwrap();
+    let value = vars
+        .entry(name.to_string())
+        .or_insert_with(|| std::env::var(name).unwrap_or_default());
+    out.write(value)?;
     Ok(())
 }
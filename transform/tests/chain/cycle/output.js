@@ -0,0 +1 @@
+export { Ping } from "cycle-a";
@@ -0,0 +1 @@
+export { StarIcon, HeartIcon as Love } from "icons";
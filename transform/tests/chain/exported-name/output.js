@@ -0,0 +1,2 @@
+export { StarIcon } from "my-library/icons/star";
+export { HeartIcon as Love } from "icons";
@@ -0,0 +1,3 @@
+export { FooBar, Baz as Qux } from "my-library/legacy/components";
+export { default as Widget } from "my-library/legacy/widgets";
+export { Hook } from "my-library/hooks";
@@ -0,0 +1,4 @@
+export * as FooBar from "my-library/components/foo-bar";
+export * as Qux from "my-library/components/baz";
+export { default as Widget } from "my-library/widgets/default";
+export * as Hook from "my-library/hooks/hook";
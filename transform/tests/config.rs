@@ -0,0 +1,397 @@
+use modularize_exports::{
+    try_modularize_exports, Config, PackageConfig, QuoteStyle, Transform, TransformError,
+};
+
+fn config(key: &str, transform: modularize_exports::Transform) -> Config {
+    Config {
+        packages: vec![(
+            key.to_string(),
+            PackageConfig {
+                transform,
+                prevent_full_export: false,
+                skip_default_conversion: false,
+                ..Default::default()
+            },
+        )]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn try_modularize_exports_accepts_valid_config() {
+    let transform = Vec::from([("use(\\w*)".to_string(), "lib/{{ member }}".to_string())]);
+
+    assert!(try_modularize_exports(config("my-library/?(.*)", transform.into())).is_ok());
+}
+
+#[test]
+fn try_modularize_exports_rejects_invalid_package_key() {
+    match try_modularize_exports(config("my-library/(", "lib/{{ member }}".into())) {
+        Err(TransformError::InvalidRegex { pattern, .. }) => {
+            assert_eq!(pattern, "my-library/(")
+        }
+        _ => panic!("expected an invalid regex error"),
+    }
+}
+
+#[test]
+fn invalid_regex_message_shows_the_pattern_as_written() {
+    let err = match try_modularize_exports(config("my-library/(", "lib/{{ member }}".into())) {
+        Err(err) => err.to_string(),
+        Ok(_) => panic!("expected an invalid regex error"),
+    };
+
+    assert!(err.starts_with("invalid regex 'my-library/(': "), "{}", err);
+    assert!(!err.contains("^(?:"), "{}", err);
+}
+
+#[test]
+fn try_modularize_exports_rejects_invalid_vec_pattern() {
+    let transform = Vec::from([("(foo".to_string(), "lib/foo".to_string())]);
+
+    match try_modularize_exports(config("my-library", transform.into())) {
+        Err(TransformError::InvalidRegex { pattern, .. }) => assert_eq!(pattern, "(foo"),
+        _ => panic!("expected an invalid regex error"),
+    }
+}
+
+#[test]
+fn config_map_form_preserves_document_order() {
+    let config: Config = serde_json::from_str(
+        r#"{
+            "zeta": { "transform": "zeta/{{ member }}" },
+            "alpha": { "transform": "alpha/{{ member }}" }
+        }"#,
+    )
+    .unwrap();
+
+    let keys: Vec<&str> = config.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["zeta", "alpha"]);
+}
+
+#[test]
+fn config_array_form_preserves_order() {
+    let config: Config = serde_json::from_str(
+        r#"[
+            ["zeta", { "transform": "zeta/{{ member }}" }],
+            ["alpha", { "transform": [["foo", "alpha/foo"]] }]
+        ]"#,
+    )
+    .unwrap();
+
+    let keys: Vec<&str> = config.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["zeta", "alpha"]);
+}
+
+#[test]
+fn config_round_trips_through_json() {
+    let json = r#"{
+        "my-library/?(.*)": { "transform": "my-library/{{ matches.[1] }}/{{ member }}" },
+        "my-library-2": {
+            "transform": [["use(\\w*)", "my-library-2/hooks/{{ member }}"]],
+            "onMissing": "keep"
+        }
+    }"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+
+    let value = serde_json::to_value(&config).unwrap();
+    assert_eq!(value["packages"][0][0], "my-library/?(.*)");
+    assert_eq!(
+        value["packages"][0][1]["transform"],
+        serde_json::json!("my-library/{{ matches.[1] }}/{{ member }}")
+    );
+    assert_eq!(value["packages"][1][0], "my-library-2");
+    assert_eq!(
+        value["packages"][1][1]["transform"],
+        serde_json::json!([["use(\\w*)", "my-library-2/hooks/{{ member }}"]])
+    );
+    assert_eq!(
+        value["packages"][1][1]["onMissing"],
+        serde_json::json!("keep")
+    );
+
+    let again: Config = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&again).unwrap(), value);
+}
+
+#[test]
+fn config_round_trip_keeps_package_order() {
+    let config = Config::from_json_str(
+        r#"[
+            ["zeta", { "transform": "zeta/{{ member }}" }],
+            ["alpha", { "transform": "alpha/{{ member }}" }]
+        ]"#,
+    )
+    .unwrap();
+
+    let again = Config::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+
+    let keys: Vec<&str> = again.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["zeta", "alpha"]);
+}
+
+#[test]
+fn from_json_str_accepts_valid_config() {
+    let config = Config::from_json_str(r#"{ "my-library": { "transform": "lib/{{ member }}" } }"#);
+
+    assert_eq!(config.unwrap().packages.len(), 1);
+}
+
+#[test]
+fn from_json_str_rejects_invalid_vec_pattern() {
+    let config =
+        Config::from_json_str(r#"{ "my-library": { "transform": [["(foo", "lib/foo"]] } }"#);
+
+    match config {
+        Err(TransformError::InvalidRegex { pattern, .. }) => assert_eq!(pattern, "(foo"),
+        _ => panic!("expected an invalid regex error"),
+    }
+}
+
+#[test]
+fn from_json_str_rejects_malformed_json() {
+    let message = |json: &str| match Config::from_json_str(json) {
+        Err(err @ TransformError::InvalidConfig { .. }) => err.to_string(),
+        _ => panic!("expected an invalid config error"),
+    };
+
+    let err = message(r#"{ "my-library": { "transform": 1 } }"#);
+    assert!(err.contains("package 'my-library'"), "{}", err);
+    assert!(err.contains("`transform` template"), "{}", err);
+
+    let err = message(r#"{ "my-library": { "onMissing": "keep" } }"#);
+    assert!(err.contains("missing field `transform`"), "{}", err);
+
+    let err = message(r#"{ "packages": [], "sortOuput": true }"#);
+    assert!(err.contains("unknown field `sortOuput`"), "{}", err);
+
+    let err = message(r#"[["my-library", { "transform": 1 }]]"#);
+    assert!(err.contains("line 1 column"), "{}", err);
+}
+
+#[test]
+fn from_value_accepts_string_and_vec_transforms() {
+    let config = Config::from_value(serde_json::json!([
+        ["zeta", { "transform": "zeta/{{ member }}" }],
+        ["alpha", { "transform": [["use(\\w*)", "alpha/hooks/{{ member }}"]] }]
+    ]))
+    .unwrap();
+
+    let keys: Vec<&str> = config.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["zeta", "alpha"]);
+    assert!(matches!(
+        config.packages[0].1.transform,
+        Transform::String(_)
+    ));
+    assert!(matches!(config.packages[1].1.transform, Transform::Vec(_)));
+}
+
+#[test]
+fn from_value_map_form_is_in_key_order() {
+    let config = Config::from_value(serde_json::json!({
+        "zeta": { "transform": "zeta/{{ member }}" },
+        "alpha": { "transform": "alpha/{{ member }}" }
+    }))
+    .unwrap();
+
+    let keys: Vec<&str> = config.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["alpha", "zeta"]);
+}
+
+#[test]
+fn from_value_rejects_invalid_vec_pattern() {
+    let config = Config::from_value(serde_json::json!({
+        "my-library": { "transform": [["(foo", "lib/foo"]] }
+    }));
+
+    match config {
+        Err(TransformError::InvalidRegex { pattern, .. }) => assert_eq!(pattern, "(foo"),
+        _ => panic!("expected an invalid regex error"),
+    }
+}
+
+#[test]
+fn transform_vec_accepts_pair_and_object_rules() {
+    let rules = |json: &str| match serde_json::from_str(json).unwrap() {
+        Transform::Vec(rules) => rules,
+        _ => panic!("expected a Transform::Vec"),
+    };
+
+    let pairs =
+        rules(r#"[["use(\\w*)", "lib/hooks/{{ member }}"], ["(\\w*)", "lib/{{ member }}"]]"#);
+    let objects = rules(
+        r#"[
+            { "pattern": "use(\\w*)", "template": "lib/hooks/{{ member }}" },
+            { "pattern": "(\\w*)", "template": "lib/{{ member }}" }
+        ]"#,
+    );
+
+    assert_eq!(pairs, objects);
+    assert_eq!(
+        pairs[0],
+        (
+            "use(\\w*)".to_string(),
+            "lib/hooks/{{ member }}".to_string()
+        )
+    );
+}
+
+#[test]
+fn config_object_form_carries_global_options() {
+    let config: Config = serde_json::from_str(
+        r#"{
+            "packages": [["zeta", { "transform": "zeta/{{ member }}" }]],
+            "quoteStyle": "single"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.packages[0].0, "zeta");
+    assert_eq!(config.quote_style, QuoteStyle::Single);
+    assert_eq!(config.max_depth, 1);
+}
+
+#[test]
+fn config_package_named_packages_is_not_the_object_form() {
+    let config: Config =
+        serde_json::from_str(r#"{ "packages": { "transform": "packages/{{ member }}" } }"#)
+            .unwrap();
+
+    assert_eq!(config.packages[0].0, "packages");
+    assert_eq!(config.quote_style, QuoteStyle::Double);
+}
+
+#[test]
+fn packages_matching_lists_keys_in_priority_order() {
+    let folder = try_modularize_exports(
+        Config::from_json_str(
+            r#"[
+                ["my-library/hooks", { "transform": "lib/hooks/{{ member }}" }],
+                ["my-library.*", { "transform": "lib/{{ member }}", "matchOn": "exportedName" }],
+                ["other", { "transform": "other/{{ member }}" }],
+                ["my-library/?(.*)", { "transform": "lib/{{ member }}" }]
+            ]"#,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        folder.packages_matching("my-library/hooks"),
+        ["my-library/hooks", "my-library/?(.*)"]
+    );
+    assert_eq!(folder.packages_matching("my-library"), ["my-library/?(.*)"]);
+    assert!(folder.packages_matching("unknown").is_empty());
+}
+
+#[test]
+fn transform_object_is_a_map() {
+    match serde_json::from_str(r#"{ "useState": "react/hooks/state" }"#).unwrap() {
+        Transform::Map(map) => assert_eq!(map["useState"], "react/hooks/state"),
+        _ => panic!("expected a Transform::Map"),
+    }
+}
+
+#[test]
+fn from_json_str_ignores_unknown_fields() {
+    let config = Config::from_json_str(
+        r#"{ "my-library": { "transform": "lib/{{ member }}", "preventFullExports": true } }"#,
+    )
+    .unwrap();
+
+    assert!(!config.packages[0].1.prevent_full_export);
+}
+
+#[test]
+fn from_json_str_strict_rejects_unknown_fields() {
+    for json in [
+        r#"{ "my-library": { "transform": "lib/{{ member }}", "preventFullExports": true } }"#,
+        r#"[["my-library", { "transform": "lib/{{ member }}", "preventFullExports": true }]]"#,
+        r#"{
+            "packages": { "my-library": { "transform": "lib/{{ member }}", "preventFullExports": true } },
+            "quoteStyle": "single"
+        }"#,
+    ] {
+        match Config::from_json_str_strict(json) {
+            Err(TransformError::InvalidConfig { source }) => assert_eq!(
+                source.to_string(),
+                "unknown field `preventFullExports` of package 'my-library'"
+            ),
+            _ => panic!("expected an invalid config error"),
+        }
+    }
+}
+
+#[test]
+fn from_json_str_strict_accepts_description() {
+    let config = Config::from_json_str_strict(
+        r#"{ "my-library": { "transform": "lib/{{ member }}", "description": "Deep imports" } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.packages[0].1.description.as_deref(),
+        Some("Deep imports")
+    );
+}
+
+#[test]
+fn merge_lets_the_override_replace_duplicate_keys() {
+    let base = Config::from_json_str(
+        r#"{
+            "packages": [
+                ["my-library", { "transform": "lib/{{ member }}" }],
+                ["shared", { "transform": "base/{{ member }}" }]
+            ],
+            "quoteStyle": "single"
+        }"#,
+    )
+    .unwrap();
+    let project = Config::from_json_str(
+        r#"[
+            ["other", { "transform": "other/{{ member }}" }],
+            ["shared", { "transform": "project/{{ member }}" }]
+        ]"#,
+    )
+    .unwrap();
+
+    let merged = base.merge(project);
+
+    let keys: Vec<&str> = merged.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["other", "shared", "my-library"]);
+    match &merged.packages[1].1.transform {
+        Transform::String(s) => assert_eq!(s, "project/{{ member }}"),
+        _ => panic!("expected a Transform::String"),
+    }
+    assert_eq!(merged.quote_style, QuoteStyle::Single);
+}
+
+#[test]
+fn merge_takes_the_global_options_set_by_the_override() {
+    let base =
+        Config::from_json_str(r#"{ "packages": [], "quoteStyle": "single", "maxDepth": 3 }"#)
+            .unwrap();
+    let project = Config::from_json_str(
+        r#"{ "packages": [], "chain": true, "maxDepth": 2, "normalizeSlashes": false }"#,
+    )
+    .unwrap();
+
+    let merged = base.merge(project);
+
+    assert_eq!(merged.quote_style, QuoteStyle::Single);
+    assert!(merged.chain);
+    assert_eq!(merged.max_depth, 2);
+    assert!(!merged.normalize_slashes);
+    assert!(!merged.sort_output);
+}
+
+#[test]
+fn merge_keeps_disjoint_packages() {
+    let merged = config("my-library", "lib/{{ member }}".into())
+        .merge(config("other", "other/{{ member }}".into()));
+
+    let keys: Vec<&str> = merged.packages.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["other", "my-library"]);
+}
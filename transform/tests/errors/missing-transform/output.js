@@ -0,0 +1,2 @@
+export { foo, bar } from 'missing-transform';
+export { foo as baz } from "missing-transform/foo";
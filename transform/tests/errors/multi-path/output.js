@@ -0,0 +1 @@
+export { Button, Input } from "multi-path";
@@ -0,0 +1 @@
+export { foo, bar } from 'warn-missing';
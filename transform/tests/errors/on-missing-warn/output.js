@@ -0,0 +1,2 @@
+export { foo } from "warn-missing/foo";
+export { bar } from 'warn-missing';
@@ -0,0 +1,2 @@
+export { Button } from 'render-failed';
+export * from 'render-failed';
@@ -0,0 +1,2 @@
+export * from 'wildcard-rule';
+export { Button } from 'wildcard-rule';
@@ -1,13 +1,17 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
-use modularize_exports::{modularize_exports, PackageConfig};
-use swc_ecma_parser::{EsConfig, Syntax};
+use modularize_exports::{
+    modularize_exports, CaseBoundary, MatchMode, MatchStrategy, MatchTarget, MissingBehavior,
+    PackageConfig, QuoteStyle,
+};
+use swc_ecma_parser::{EsConfig, Syntax, TsConfig};
 use swc_ecma_transforms_testing::{test_fixture, FixtureTestConfig};
 use testing::fixture;
 
 fn syntax() -> Syntax {
     Syntax::Es(EsConfig {
         jsx: true,
+        import_attributes: true,
         ..Default::default()
     })
 }
@@ -26,6 +30,7 @@ fn modularize_exports_fixture(input: PathBuf) {
                             transform: "react-bootstrap/lib/{{member}}".into(),
                             prevent_full_export: false,
                             skip_default_conversion: false,
+                            ..Default::default()
                         },
                     ),
                     (
@@ -34,6 +39,7 @@ fn modularize_exports_fixture(input: PathBuf) {
                             transform: "my-library/{{ matches.[1] }}/{{member}}".into(),
                             prevent_full_export: false,
                             skip_default_conversion: false,
+                            ..Default::default()
                         },
                     ),
                     (
@@ -42,6 +48,7 @@ fn modularize_exports_fixture(input: PathBuf) {
                             transform: "my-library-2/{{ camelCase member }}".into(),
                             prevent_full_export: false,
                             skip_default_conversion: true,
+                            ..Default::default()
                         },
                     ),
                     (
@@ -50,6 +57,7 @@ fn modularize_exports_fixture(input: PathBuf) {
                             transform: "my-library-3/{{ kebabCase member }}".into(),
                             prevent_full_export: false,
                             skip_default_conversion: true,
+                            ..Default::default()
                         },
                     ),
                     (
@@ -76,11 +84,899 @@ fn modularize_exports_fixture(input: PathBuf) {
                             .into(),
                             prevent_full_export: false,
                             skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-5".to_string(),
+                        PackageConfig {
+                            transform: "my-library-5/{{ snakeCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-6".to_string(),
+                        PackageConfig {
+                            transform: "my-library-6/{{ pascalCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-7".to_string(),
+                        PackageConfig {
+                            transform: "my-library-7/{{ constantCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-8".to_string(),
+                        PackageConfig {
+                            transform:
+                                "my-library-8/{{ upperFirst member }}/{{ lowerFirst member }}"
+                                    .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-9".to_string(),
+                        PackageConfig {
+                            transform: "my-library-9/{{ replace member \"Icon$\" \"\" }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-10/?(\\w*)".to_string(),
+                        PackageConfig {
+                            transform:
+                                "my-library-10/{{ default matches.[1] \"core\" }}/{{ member }}"
+                                    .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-11".to_string(),
+                        PackageConfig {
+                            transform: "my-library-11/{{ trimPrefix (trimSuffix member \"Component\") \"with\" }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-12".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([(
+                                "use(\\w*)".to_string(),
+                                "my-library-12/hooks/{{ kebabCase memberMatches.[1] }}".to_string(),
+                            )])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            on_missing: MissingBehavior::Keep,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-13/?(.*)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-13/{{ matches.[1] }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-14".to_string(),
+                        PackageConfig {
+                            transform: "https://cdn.example.com//my-library-14//{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-15".to_string(),
+                        PackageConfig {
+                            transform: "my-library-15/{{ memberMatches.[1] }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            member_pattern: Some("(\\w+?)(Icon)?".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "shared-(a|b)".to_string(),
+                        PackageConfig {
+                            transform: "{{ packageName }}/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "shared-vec-(a|b)".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([(
+                                ".*".to_string(),
+                                "{{ packageName }}/{{ member }}".to_string(),
+                            )])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "ordered-(\\w+)".to_string(),
+                        PackageConfig {
+                            transform: "ordered-{{ matches.[1] }}/first/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "ordered-pkg".to_string(),
+                        PackageConfig {
+                            transform: "ordered-pkg/second/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-17".to_string(),
+                        PackageConfig {
+                            transform: "my-library-17/{{ member }}/{{ exportedName }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-18".to_string(),
+                        PackageConfig {
+                            transform: "my-library-18/{{ member }}".into(),
+                            prevent_full_export: true,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-19".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                (
+                                    "use(\\w*)".to_string(),
+                                    "/my-library-19/hooks/{{ kebabCase memberMatches.[1] }}"
+                                        .to_string(),
+                                ),
+                                ("(\\w*)".to_string(), "my-library-19/{{ member }}".to_string()),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            prefix: Some("dist/".to_string()),
+                            suffix: Some(".js".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-20".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                ("Legacy".to_string(), "my-library-20/lib/Legacy.js".to_string()),
+                                (
+                                    "(\\w*)".to_string(),
+                                    "my-library-20//lib/{{ member }}".to_string(),
+                                ),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            extension: Some(".js".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-21/?(.*)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-21/{{ matches.[1] }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            strip_extensions: vec![".jsx".to_string()],
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-22".to_string(),
+                        PackageConfig {
+                            transform: "my-library-22/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            emit_import_export: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-23/?(\\w*)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-23/{{ lowerCase matches.[1] }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            case_insensitive: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-24".to_string(),
+                        PackageConfig {
+                            transform: "my-library-24/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            exclude: vec!["version".to_string(), "(\\w*)Config".to_string()],
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-25".to_string(),
+                        PackageConfig {
+                            transform: "my-library-25/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            include: Some(vec!["use(\\w*)".to_string(), "Button".to_string()]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-26".to_string(),
+                        PackageConfig {
+                            transform: "my-library-26/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            include: Some(vec![]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-27".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                ("(\\w*)".to_string(), "my-library-27/lib/{{ member }}".to_string()),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            all_transform: Some(
+                                "my-library-27/lib\nmy-library-27/types".into(),
+                            ),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-28/(?<area>\\w+)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-28/{{ matches.area }}/{{ memberMatches.name }}/{{ matches.[1] }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            member_pattern: Some("use(?<name>\\w+)".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-29".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([(
+                                "use(\\w*)".to_string(),
+                                "my-library-29/hooks/{{ member }}".to_string(),
+                            )])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            fallback: Some("my-library-29/lib/{{ kebabCase member }}".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-30".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                ("use(\\w*)".to_string(), "my-library-30/lib/hooks".to_string()),
+                                ("(\\w*)".to_string(), "my-library-30/lib/{{ member }}".to_string()),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            merge_same_source: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-31".to_string(),
+                        PackageConfig {
+                            transform: "{{#if (eq member \"index\")}}my-library-31{{else}}{{#if (ne exportedName member)}}my-library-31/aliased/{{ member }}{{else}}my-library-31/lib/{{ member }}{{/if}}{{/if}}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-32".to_string(),
+                        PackageConfig {
+                            transform: "{{ env \"CARGO_PKG_NAME\" }}/{{ env \"TRANSFORM_EXPORTS_UNSET_VAR\" }}/my-library-32/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-33".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                ("(\\w*)".to_string(), "my-library-33/lib/{{ member }}".to_string()),
+                                (
+                                    "use(\\w*)".to_string(),
+                                    "my-library-33/hooks/{{ kebabCase memberMatches.[1] }}".to_string(),
+                                ),
+                                ("useTheme".to_string(), "my-library-33/theme".to_string()),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            match_strategy: MatchStrategy::LongestMatch,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-34".to_string(),
+                        PackageConfig {
+                            transform: "my-library-34/{{ titleCase member }}/{{ sentenceCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-35".to_string(),
+                        PackageConfig {
+                            transform: "my-library-35//{{ pathCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-36".to_string(),
+                        PackageConfig {
+                            transform: "my-library-36/icons.{{ dotCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-37".to_string(),
+                        PackageConfig {
+                            transform: "my-library-37/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            all_transform: Some("my-library-37/index".into()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-38".to_string(),
+                        PackageConfig {
+                            transform: "my-library-38/{{#if (eq index 0)}}first/{{/if}}{{ index }}-of-{{ total }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-39".to_string(),
+                        PackageConfig {
+                            transform: "{{#if isDefault}}my-library-39{{else}}my-library-39/lib/{{ member }}{{/if}}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-40/**".to_string(),
+                        PackageConfig {
+                            transform: "my-library-40/{{ default matches.[1] \"core\" }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            match_mode: MatchMode::Glob,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "@scope/*-icons".to_string(),
+                        PackageConfig {
+                            transform: "@scope/{{ matches.[1] }}-icons/svg/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            match_mode: MatchMode::Glob,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-41(?:/(\\w+))?(?:/(?P<leaf>\\w+))?".to_string(),
+                        PackageConfig {
+                            transform: "my-library-41/lib/{{ join matches \"/\" }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-42-".to_string(),
+                        PackageConfig {
+                            transform: "my-library-42/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            anchored: false,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-43".to_string(),
+                        PackageConfig {
+                            transform: HashMap::from([
+                                (
+                                    "useState".to_string(),
+                                    "my-library-43/hooks/state".to_string(),
+                                ),
+                                (
+                                    "useEffect".to_string(),
+                                    "my-library-43/hooks/effect".to_string(),
+                                ),
+                                (
+                                    "use.*".to_string(),
+                                    "my-library-43/{{ member }}".to_string(),
+                                ),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            fallback: Some("my-library-43/{{ camelCase member }}".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-44".to_string(),
+                        PackageConfig {
+                            transform: "my-library-44/{{#if (lookup data member)}}{{ lookup data member }}{{else}}lib/{{ member }}{{/if}}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            data: Some(serde_json::json!({ "Button": "buttons/base", "IconButton": "buttons/icon" })),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-45".to_string(),
+                        PackageConfig {
+                            transform: "my-library-45/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            default_member: Some("index".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-46".to_string(),
+                        PackageConfig {
+                            transform: "my-library-46/{{#if (startsWith member \"use\")}}hooks{{else}}{{#if (endsWith member \"Provider\")}}providers{{else}}components{{/if}}{{/if}}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-47".to_string(),
+                        PackageConfig {
+                            transform: "my-library-47/dist/barrel".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            merge_same_source: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "use(\\w+)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-48/hooks/{{ kebabCase matches.[1] }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            match_on: MatchTarget::ExportedName,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-49/(?:legacy/(\\w+)|modern/(\\w+)|\\w+)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-49/lib/{{ firstNonEmpty matches.[1] matches.[2] \"core\" }}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-50".to_string(),
+                        PackageConfig {
+                            transform: "my-library-50/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            known_members: Some(vec!["Button".to_string(), "Card".to_string(), "icon-set".to_string()]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-51".to_string(),
+                        PackageConfig {
+                            transform: "my-library-51/lib?member={{ urlEncode member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-52".to_string(),
+                        PackageConfig {
+                            transform: "my-library-52/{{ kebabCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            case_boundaries: Some(vec![CaseBoundary::LowerUpper, CaseBoundary::LowerDigit]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-53/?(.*)".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([(
+                                "(\\w+)Icon".to_string(),
+                                "my-library-53/{{ matches.[1] }}/icons/{{ kebabCase \
+                                 memberMatches.[1] }}"
+                                    .to_string(),
+                            )])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-54(?:/(.*))?".to_string(),
+                        PackageConfig {
+                            transform: "my-library-54/{{#if (eq (length matches) 2)}}{{ matches.[1] }}{{else}}main{{/if}}/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-55".to_string(),
+                        PackageConfig {
+                            transform: "my-library-55/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            all_transform: Some("my-library-55/index".into()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-56/?(.*)".to_string(),
+                        PackageConfig {
+                            transform: "my-library-56/{{#if with.type}}{{ with.type }}{{else}}js{{/if}}/{{ matches.[1] }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-57".to_string(),
+                        PackageConfig {
+                            transform: "my-library-57//{{ reverseWords member }}//index".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-58".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                (".*".to_string(), "my-library-58/generic".to_string()),
+                                ("Special".to_string(), "my-library-58/specific".to_string()),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            match_strategy: MatchStrategy::LongestMatch,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library-59".to_string(),
+                        PackageConfig {
+                            transform: "my-library-59/lib/{{ member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            known_members: Some(vec!["Button".to_string(), "Card".to_string()]),
+                            exclude: vec!["Card".to_string()],
+                            ..Default::default()
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            })
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/**/input.js")]
+fn modularize_exports_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            modularize_exports(modularize_exports::Config {
+                packages: vec![
+                    (
+                        "missing-transform".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([(
+                                "foo".to_string(),
+                                "missing-transform/foo".to_string(),
+                            )])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "render-failed".to_string(),
+                        PackageConfig {
+                            transform: "render-failed/{{ member".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "warn-missing".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([(
+                                "foo".to_string(),
+                                "warn-missing/foo".to_string(),
+                            )])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            on_missing: MissingBehavior::Warn,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "wildcard-rule".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                (
+                                    "(\\w*)".to_string(),
+                                    "wildcard-rule/lib/{{ member }}".to_string(),
+                                ),
+                                (
+                                    "(.*)".to_string(),
+                                    "wildcard-rule/other/{{ member }}".to_string(),
+                                ),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "multi-path".to_string(),
+                        PackageConfig {
+                            transform: Vec::from([
+                                (
+                                    "Button".to_string(),
+                                    "multi-path/lib/{{ member }}\nmulti-path/types/{{ member }}"
+                                        .to_string(),
+                                ),
+                                (
+                                    "(\\w*)".to_string(),
+                                    "multi-path/lib/{{ member }}".to_string(),
+                                ),
+                            ])
+                            .into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
                         },
                     ),
                 ]
                 .into_iter()
                 .collect(),
+                ..Default::default()
+            })
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/single-quote/**/input.js")]
+fn modularize_exports_single_quote_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            modularize_exports(modularize_exports::Config {
+                packages: vec![(
+                    "react-bootstrap".to_string(),
+                    PackageConfig {
+                        transform: "react-bootstrap/lib/{{member}}".into(),
+                        prevent_full_export: false,
+                        skip_default_conversion: false,
+                        ..Default::default()
+                    },
+                )],
+                quote_style: QuoteStyle::Single,
+                ..Default::default()
+            })
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/sort-output/**/input.js")]
+fn modularize_exports_sort_output_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            modularize_exports(modularize_exports::Config {
+                packages: vec![(
+                    "react-bootstrap".to_string(),
+                    PackageConfig {
+                        transform: "react-bootstrap/lib/{{member}}".into(),
+                        prevent_full_export: false,
+                        skip_default_conversion: false,
+                        ..Default::default()
+                    },
+                )],
+                sort_output: true,
+                ..Default::default()
+            })
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/chain/**/input.js")]
+fn modularize_exports_chain_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            modularize_exports(modularize_exports::Config {
+                packages: vec![
+                    (
+                        "my-library/legacy/?(.*)".to_string(),
+                        PackageConfig {
+                            transform: "my-library/{{ matches.[1] }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "my-library/(components|widgets|hooks)".to_string(),
+                        PackageConfig {
+                            transform: "my-library/{{ matches.[1] }}/{{ kebabCase member }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: false,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "cycle-a".to_string(),
+                        PackageConfig {
+                            transform: "cycle-b".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "cycle-b".to_string(),
+                        PackageConfig {
+                            transform: "cycle-a".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "(\\w+)Icon".to_string(),
+                        PackageConfig {
+                            transform: "my-library/icons/{{ kebabCase matches.[1] }}".into(),
+                            prevent_full_export: false,
+                            skip_default_conversion: true,
+                            match_on: MatchTarget::ExportedName,
+                            ..Default::default()
+                        },
+                    ),
+                ],
+                chain: true,
+                ..Default::default()
+            })
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/typescript/**/input.ts")]
+fn modularize_exports_typescript_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.ts");
+    test_fixture(
+        Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        }),
+        &|_tr| {
+            modularize_exports(modularize_exports::Config {
+                packages: vec![(
+                    "react-bootstrap".to_string(),
+                    PackageConfig {
+                        transform: "react-bootstrap/lib/{{member}}".into(),
+                        prevent_full_export: false,
+                        skip_default_conversion: false,
+                        ..Default::default()
+                    },
+                )],
+                ..Default::default()
             })
         },
         &input,
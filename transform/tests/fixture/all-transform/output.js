@@ -0,0 +1,2 @@
+export * from "my-library-37/index";
+export { Button } from "my-library-37/lib/Button";
@@ -0,0 +1 @@
+export { Button as default, Row } from "react-bootstrap";
@@ -0,0 +1,2 @@
+export { default } from "react-bootstrap/lib/Button";
+export * as Row from "react-bootstrap/lib/Row";
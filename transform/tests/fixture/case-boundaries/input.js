@@ -0,0 +1,2 @@
+export { APIKey, HTTPClient, Vector3D } from "my-library-52";
+export { APIKey, HTTPClient, Vector3D } from "my-library-3";
@@ -0,0 +1,6 @@
+export { APIKey } from "my-library-52/apikey";
+export { HTTPClient } from "my-library-52/httpclient";
+export { Vector3D } from "my-library-52/vector-3d";
+export { APIKey } from "my-library-3/api-key";
+export { HTTPClient } from "my-library-3/http-client";
+export { Vector3D } from "my-library-3/vector-3-d";
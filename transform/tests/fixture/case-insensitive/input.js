@@ -0,0 +1,3 @@
+export { Button } from "my-library-23";
+export { Input } from "My-Library-23/Forms";
+export { Modal } from "MY-LIBRARY-23/overlays";
@@ -0,0 +1,3 @@
+export { Button } from "my-library-23/Button";
+export { Input } from "my-library-23/forms/Input";
+export { Modal } from "my-library-23/overlays/Modal";
@@ -0,0 +1 @@
+export { apiKey, BaseUrl } from 'my-library-7';
@@ -0,0 +1,2 @@
+export { apiKey } from "my-library-7/API_KEY";
+export { BaseUrl } from "my-library-7/BASE_URL";
@@ -0,0 +1,2 @@
+export { default } from 'react-bootstrap';
+export { default as Foo } from 'react-bootstrap';
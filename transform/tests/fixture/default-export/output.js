@@ -0,0 +1,2 @@
+export { default } from "react-bootstrap/lib/default";
+export { default as Foo } from "react-bootstrap/lib/default";
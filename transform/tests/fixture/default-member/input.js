@@ -0,0 +1,2 @@
+export { default as Button, default as Card, Alert } from "my-library-45";
+export { default } from "my-library-45";
@@ -0,0 +1,4 @@
+export { default as Button } from "my-library-45/lib/Button";
+export { default as Card } from "my-library-45/lib/Card";
+export * as Alert from "my-library-45/lib/Alert";
+export { default } from "my-library-45/lib/index";
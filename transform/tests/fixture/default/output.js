@@ -0,0 +1,2 @@
+export { Button } from "my-library-10/core/Button";
+export { Card } from "my-library-10/extra/Card";
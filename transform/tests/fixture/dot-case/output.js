@@ -0,0 +1 @@
+export { solidHome } from "my-library-36/icons.solid.home";
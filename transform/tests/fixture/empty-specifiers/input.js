@@ -0,0 +1,2 @@
+export {} from 'react-bootstrap';
+export { Button } from 'react-bootstrap';
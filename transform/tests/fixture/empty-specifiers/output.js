@@ -0,0 +1,2 @@
+export { } from 'react-bootstrap';
+export * as Button from "react-bootstrap/lib/Button";
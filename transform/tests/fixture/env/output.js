@@ -0,0 +1 @@
+export { Button } from "modularize_exports/my-library-32/Button";
@@ -0,0 +1 @@
+export { index, Button, Input as TextInput } from "my-library-31";
@@ -0,0 +1,3 @@
+export { index } from "my-library-31";
+export { Button } from "my-library-31/lib/Button";
+export { Input as TextInput } from "my-library-31/aliased/Input";
@@ -0,0 +1 @@
+export { Button, version, ThemeConfig, Input } from "my-library-24";
@@ -0,0 +1,4 @@
+export { Button } from "my-library-24/lib/Button";
+export { version } from "my-library-24";
+export { ThemeConfig } from "my-library-24";
+export { Input } from "my-library-24/lib/Input";
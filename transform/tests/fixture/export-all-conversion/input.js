@@ -0,0 +1,4 @@
+export * from 'my-library-55';
+export { Button } from 'my-library-55';
+export * from 'my-library-37';
+export { Button as Button37 } from 'my-library-37';
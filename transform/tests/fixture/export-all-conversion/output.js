@@ -0,0 +1,4 @@
+export * from "my-library-55/index";
+export * as Button from "my-library-55/lib/Button";
+export * from "my-library-37/index";
+export { Button as Button37 } from "my-library-37/lib/Button";
@@ -0,0 +1 @@
+export { Button as Btn, Card } from 'my-library-17';
@@ -0,0 +1,2 @@
+export * as Btn from "my-library-17/Button/Btn";
+export * as Card from "my-library-17/Card/Card";
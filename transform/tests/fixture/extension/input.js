@@ -0,0 +1 @@
+export { Button, Legacy } from 'my-library-20';
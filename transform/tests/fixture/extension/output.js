@@ -0,0 +1,2 @@
+export { Button } from "my-library-20/lib/Button.js";
+export { Legacy } from "my-library-20/lib/Legacy.js";
@@ -0,0 +1 @@
+export { useTheme, DatePicker } from "my-library-29";
@@ -0,0 +1,2 @@
+export { useTheme } from "my-library-29/hooks/useTheme";
+export { DatePicker } from "my-library-29/lib/date-picker";
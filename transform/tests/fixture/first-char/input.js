@@ -0,0 +1 @@
+export { someLongName, X } from 'my-library-8';
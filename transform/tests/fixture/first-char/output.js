@@ -0,0 +1,2 @@
+export { someLongName } from "my-library-8/SomeLongName/someLongName";
+export { X } from "my-library-8/X/x";
@@ -0,0 +1,3 @@
+export { Foo } from "my-library-49/legacy/forms";
+export { Bar } from "my-library-49/modern/layout";
+export { Baz } from "my-library-49/other";
@@ -0,0 +1,3 @@
+export { Foo } from "my-library-49/lib/forms/Foo";
+export { Bar } from "my-library-49/lib/layout/Bar";
+export { Baz } from "my-library-49/lib/core/Baz";
@@ -0,0 +1 @@
+export { Button, Card as Panel, default as Theme, useTheme } from "my-library-47";
@@ -0,0 +1,3 @@
+export { Button } from "my-library-40";
+export { Modal } from "my-library-40/overlays/modal";
+export { Home } from "@scope/solid-icons";
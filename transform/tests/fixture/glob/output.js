@@ -0,0 +1,3 @@
+export { Button } from "my-library-40/core/Button";
+export { Modal } from "my-library-40/overlays/modal/Modal";
+export { Home } from "@scope/solid-icons/svg/Home";
@@ -0,0 +1,4 @@
+export { default as theme } from 'my-library-56/theme' with { type: 'css' };
+export { default as config } from 'my-library-56/config' with { type: 'json' };
+export { default as utils } from 'my-library-56/utils';
+export * from 'my-library-56/tokens' with { type: 'json' };
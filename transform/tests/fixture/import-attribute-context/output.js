@@ -0,0 +1,10 @@
+export { default as theme } from "my-library-56/css/theme" with {
+    type: 'css'
+};
+export { default as config } from "my-library-56/json/config" with {
+    type: 'json'
+};
+export { default as utils } from "my-library-56/js/utils";
+export * from "my-library-56/json/tokens" with {
+    type: 'json'
+};
@@ -0,0 +1,3 @@
+export { default as data } from 'react-bootstrap' with { type: 'json' };
+export { Grid } from 'react-bootstrap' with { type: 'json' };
+export * from 'react-bootstrap' with { type: 'json' };
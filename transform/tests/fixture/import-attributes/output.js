@@ -0,0 +1,9 @@
+export { default as data } from "react-bootstrap/lib/default" with {
+    type: 'json'
+};
+export * as Grid from "react-bootstrap/lib/Grid" with {
+    type: 'json'
+};
+export * from "react-bootstrap/lib/*" with {
+    type: 'json'
+};
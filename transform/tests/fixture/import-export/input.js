@@ -0,0 +1,3 @@
+const _Button = 1;
+export { Button, Input as TextInput, default } from "my-library-22";
+export { _Button };
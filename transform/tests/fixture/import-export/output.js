@@ -0,0 +1,8 @@
+const _Button = 1;
+import * as _Button1 from "my-library-22/Button";
+export { _Button1 as Button };
+import * as _TextInput from "my-library-22/Input";
+export { _TextInput as TextInput };
+import { default as _default } from "my-library-22/default";
+export { _default as default };
+export { _Button };
@@ -0,0 +1,2 @@
+export { Button, useTheme, version } from "my-library-25";
+export { Button as Button2 } from "my-library-26";
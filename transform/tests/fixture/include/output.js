@@ -0,0 +1,4 @@
+export { Button } from "my-library-25/lib/Button";
+export { useTheme } from "my-library-25/lib/useTheme";
+export { version } from "my-library-25";
+export { Button as Button2 } from "my-library-26";
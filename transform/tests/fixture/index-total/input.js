@@ -0,0 +1,2 @@
+export { Button, Input, Modal } from "my-library-38";
+export * as Row from "my-library-38";
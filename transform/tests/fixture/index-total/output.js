@@ -0,0 +1,4 @@
+export { Button } from "my-library-38/first/0-of-3/Button";
+export { Input } from "my-library-38/1-of-3/Input";
+export { Modal } from "my-library-38/2-of-3/Modal";
+export * as Row from "my-library-38/first/0-of-1/Row";
@@ -0,0 +1 @@
+export { default, Button } from "my-library-39";
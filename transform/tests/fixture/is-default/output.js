@@ -0,0 +1,2 @@
+export { default } from "my-library-39";
+export { Button } from "my-library-39/lib/Button";
@@ -0,0 +1,3 @@
+export { Foo } from "my-library-41";
+export { Bar } from "my-library-41/core";
+export { Baz } from "my-library-41/core/button";
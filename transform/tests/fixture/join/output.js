@@ -0,0 +1,3 @@
+export { Foo } from "my-library-41/lib/my-library-41/Foo";
+export { Bar } from "my-library-41/lib/my-library-41/core/core/Bar";
+export { Baz } from "my-library-41/lib/my-library-41/core/button/core/button/Baz";
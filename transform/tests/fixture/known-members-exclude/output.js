@@ -0,0 +1,2 @@
+export * as Button from "my-library-59/lib/Button";
+export { Card } from 'my-library-59';
@@ -0,0 +1,2 @@
+export * from "my-library-50";
+export * from "my-library-22";
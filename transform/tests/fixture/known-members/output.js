@@ -0,0 +1,4 @@
+export * as Button from "my-library-50/lib/Button";
+export * as Card from "my-library-50/lib/Card";
+export * as "icon-set" from "my-library-50/lib/icon-set";
+export * from "my-library-22/*";
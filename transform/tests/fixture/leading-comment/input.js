@@ -0,0 +1,5 @@
+/* license */
+// eslint-disable-next-line
+export { Button, Row } from "react-bootstrap";
+// keep me
+export { foo } from "my-library-4";
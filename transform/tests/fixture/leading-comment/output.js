@@ -0,0 +1,5 @@
+/* license */ // eslint-disable-next-line
+export * as Button from "react-bootstrap/lib/Button";
+export * as Row from "react-bootstrap/lib/Row";
+// keep me
+export { foo } from "my-library-4/this_is_foo";
@@ -0,0 +1,2 @@
+export { Button } from 'my-library-54';
+export { Slider } from 'my-library-54/sub';
@@ -0,0 +1,2 @@
+export * as Button from "my-library-54/main/Button";
+export * as Slider from "my-library-54/sub/Slider";
@@ -0,0 +1,2 @@
+export default function App() {}
+export { Button } from 'react-bootstrap';
@@ -0,0 +1,2 @@
+export default function App() {}
+export * as Button from "react-bootstrap/lib/Button";
@@ -0,0 +1,3 @@
+import { Grid } from 'react-bootstrap';
+export default Grid;
+export * as Button from "react-bootstrap/lib/Button";
@@ -0,0 +1,3 @@
+export const version = '1.0.0';
+export function helper() {}
+export { Button } from "my-library-18/Button";
@@ -0,0 +1,2 @@
+export { Button, useMediaQuery, useTheme } from "my-library-33";
+export { Special, Other } from "my-library-58";
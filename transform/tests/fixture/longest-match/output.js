@@ -0,0 +1,5 @@
+export { Button } from "my-library-33/lib/Button";
+export { useMediaQuery } from "my-library-33/hooks/media-query";
+export { useTheme } from "my-library-33/theme";
+export { Special } from "my-library-58/specific";
+export { Other } from "my-library-58/generic";
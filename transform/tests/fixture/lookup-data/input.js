@@ -0,0 +1 @@
+export { Button, IconButton, Card } from "my-library-44";
@@ -0,0 +1,3 @@
+export { Button } from "my-library-44/buttons/base";
+export { IconButton } from "my-library-44/buttons/icon";
+export { Card } from "my-library-44/lib/Card";
@@ -0,0 +1 @@
+export { useState, useEffect as useMountEffect, useMemo, Context } from "my-library-43";
@@ -0,0 +1,4 @@
+export { useState } from "my-library-43/hooks/state";
+export { useEffect as useMountEffect } from "my-library-43/hooks/effect";
+export { useMemo } from "my-library-43/useMemo";
+export { Context } from "my-library-43/context";
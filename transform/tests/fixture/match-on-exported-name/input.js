@@ -0,0 +1,5 @@
+export { useTheme, Button, useFetch as useRequest } from "./internal";
+export * from "./internal";
+export * as useAll from "./internal";
+export { useGrid, Card } from "my-library-52";
+export { Card as OtherCard } from "my-library-52";
@@ -0,0 +1,8 @@
+export { useTheme } from "my-library-48/hooks/theme";
+export { Button } from "./internal";
+export { useFetch as useRequest } from "my-library-48/hooks/request";
+export * from "./internal";
+export * as useAll from "my-library-48/hooks/all";
+export { useGrid } from "my-library-48/hooks/grid";
+export { Card } from "my-library-52/card";
+export { Card as OtherCard } from "my-library-52/card";
@@ -0,0 +1 @@
+export { ArrowIcon, Home } from 'my-library-15';
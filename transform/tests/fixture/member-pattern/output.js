@@ -0,0 +1,2 @@
+export { ArrowIcon } from "my-library-15/Arrow";
+export { Home } from "my-library-15/Home";
@@ -0,0 +1 @@
+export { useFoo, Button, useBar as useBaz } from "my-library-30";
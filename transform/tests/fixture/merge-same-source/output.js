@@ -0,0 +1,2 @@
+export { useFoo, useBar as useBaz } from "my-library-30/lib/hooks";
+export { Button } from "my-library-30/lib/Button";
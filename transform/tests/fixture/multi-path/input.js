@@ -0,0 +1,2 @@
+export * from "my-library-27";
+export { Input } from "my-library-27";
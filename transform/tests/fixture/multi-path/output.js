@@ -0,0 +1,3 @@
+export * from "my-library-27/lib";
+export * from "my-library-27/types";
+export { Input } from "my-library-27/lib/Input";
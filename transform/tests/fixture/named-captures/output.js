@@ -0,0 +1 @@
+export { useTheme } from "my-library-28/hooks/Theme/hooks";
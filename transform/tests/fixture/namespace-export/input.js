@@ -0,0 +1,2 @@
+export * as Grid from 'react-bootstrap';
+export * as useSomeAPI from 'my-library-4';
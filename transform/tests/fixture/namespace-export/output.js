@@ -0,0 +1,2 @@
+export * as Grid from "react-bootstrap/lib/Grid";
+export * as useSomeAPI from "my-library-4/use-some-api/some-api";
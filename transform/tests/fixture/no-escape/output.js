@@ -0,0 +1 @@
+export { Query } from "my-library-13/a&b/Query";
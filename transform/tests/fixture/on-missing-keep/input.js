@@ -0,0 +1 @@
+export { useTheme, Button, useMediaQuery as useMedia } from 'my-library-12';
@@ -0,0 +1,3 @@
+export { useTheme } from "my-library-12/hooks/theme";
+export { Button } from 'my-library-12';
+export { useMediaQuery as useMedia } from "my-library-12/hooks/media-query";
@@ -0,0 +1 @@
+export { Button } from "ordered-pkg/first/Button";
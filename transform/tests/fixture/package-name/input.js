@@ -0,0 +1,3 @@
+export { Button } from 'shared-a';
+export { Card } from 'shared-b';
+export { Icon } from 'shared-vec-b';
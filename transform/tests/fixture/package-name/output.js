@@ -0,0 +1,3 @@
+export { Button } from "shared-a/lib/Button";
+export { Card } from "shared-b/lib/Card";
+export { Icon } from "shared-vec-b/Icon";
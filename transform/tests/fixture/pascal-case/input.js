@@ -0,0 +1 @@
+export { button, myWidget, Dialog_box } from 'my-library-6';
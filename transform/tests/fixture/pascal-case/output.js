@@ -0,0 +1,3 @@
+export { button } from "my-library-6/Button";
+export { myWidget } from "my-library-6/MyWidget";
+export { Dialog_box } from "my-library-6/DialogBox";
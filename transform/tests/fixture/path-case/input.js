@@ -0,0 +1 @@
+export { UserProfileSettings, Button } from "my-library-35";
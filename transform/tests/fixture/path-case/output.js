@@ -0,0 +1,2 @@
+export { UserProfileSettings } from "my-library-35/user/profile/settings";
+export { Button } from "my-library-35/button";
@@ -0,0 +1 @@
+export { useTheme, Button } from 'my-library-19';
@@ -0,0 +1,2 @@
+export { useTheme } from "dist/my-library-19/hooks/theme.js";
+export { Button } from "dist/my-library-19/Button.js";
@@ -0,0 +1,2 @@
+export { Button, useTheme as useAppTheme } from 'my-library-46';
+export { Card } from "my-library-46";
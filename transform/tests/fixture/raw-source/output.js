@@ -0,0 +1,3 @@
+export { Button } from "my-library-46/components/Button";
+export { useTheme as useAppTheme } from "my-library-46/hooks/useTheme";
+export { Card } from "my-library-46/components/Card";
@@ -0,0 +1 @@
+export { HomeIcon, IconButton } from 'my-library-9';
@@ -0,0 +1,2 @@
+export { HomeIcon } from "my-library-9/Home";
+export { IconButton } from "my-library-9/IconButton";
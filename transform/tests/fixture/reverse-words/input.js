@@ -0,0 +1 @@
+export { fooBarBaz, Button } from 'my-library-57';
@@ -0,0 +1,2 @@
+export * as fooBarBaz from "my-library-57/baz/bar/foo/index";
+export * as Button from "my-library-57/button/index";
@@ -0,0 +1 @@
+export { Button as PrimaryButton, Button as SecondaryButton, Button } from "my-library-17";
@@ -0,0 +1,3 @@
+export * as PrimaryButton from "my-library-17/Button/PrimaryButton";
+export * as SecondaryButton from "my-library-17/Button/SecondaryButton";
+export * as Button from "my-library-17/Button/Button";
@@ -0,0 +1 @@
+export { Button } from 'my-library-14';
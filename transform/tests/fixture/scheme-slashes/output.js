@@ -0,0 +1 @@
+export { Button } from "https://cdn.example.com/my-library-14/Button";
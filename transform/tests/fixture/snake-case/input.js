@@ -0,0 +1 @@
+export { FooBar, bazQux } from 'my-library-5';
@@ -0,0 +1,2 @@
+export { FooBar } from "my-library-5/foo_bar";
+export { bazQux } from "my-library-5/baz_qux";
@@ -0,0 +1 @@
+export { useTheme, ThemeProvider, Button } from "my-library-46";
@@ -0,0 +1,3 @@
+export { useTheme } from "my-library-46/hooks/useTheme";
+export { ThemeProvider } from "my-library-46/providers/ThemeProvider";
+export { Button } from "my-library-46/components/Button";
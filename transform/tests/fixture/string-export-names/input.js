@@ -0,0 +1,3 @@
+export { "foo bar" as Baz, Qux as "qux-quux", "a-b" } from "react-bootstrap";
+export * as "ns name" from "my-library-22";
+export { "foo bar" as "x y", Button as "default" } from "my-library-22";
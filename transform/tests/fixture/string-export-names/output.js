@@ -0,0 +1,9 @@
+export * as Baz from "react-bootstrap/lib/foo bar";
+export * as "qux-quux" from "react-bootstrap/lib/Qux";
+export * as "a-b" from "react-bootstrap/lib/a-b";
+import * as _ns_name from "my-library-22/ns name";
+export { _ns_name as "ns name" };
+import * as _x_y from "my-library-22/foo bar";
+export { _x_y as "x y" };
+import { default as _default } from "my-library-22/Button";
+export { _default as default };
@@ -0,0 +1,2 @@
+export { Button } from "my-library-21/components/Button.jsx";
+export { Input } from "my-library-21/components/Input";
@@ -0,0 +1,2 @@
+export { Button } from "my-library-21/components/Button/Button";
+export { Input } from "my-library-21/components/Input/Input";
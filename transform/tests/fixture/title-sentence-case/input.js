@@ -0,0 +1 @@
+export { myCoolButton } from "my-library-34";
@@ -0,0 +1 @@
+export { myCoolButton } from "my-library-34/My Cool Button/My cool button";
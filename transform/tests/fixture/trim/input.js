@@ -0,0 +1 @@
+export { ButtonComponent, withRouter, Card } from 'my-library-11';
@@ -0,0 +1,3 @@
+export { ButtonComponent } from "my-library-11/Button";
+export { withRouter } from "my-library-11/Router";
+export { Card } from "my-library-11/Card";
@@ -0,0 +1,3 @@
+export { Foo } from "my-library-42-core";
+export { Bar } from "@scope/my-library-42-utils";
+export { Baz } from "my-library-42";
@@ -0,0 +1,3 @@
+export { Foo } from "my-library-42/lib/Foo";
+export { Bar } from "my-library-42/lib/Bar";
+export { Baz } from "my-library-42";
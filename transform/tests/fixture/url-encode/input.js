@@ -0,0 +1 @@
+export { "foo bar" as fooBar, "a//b" as ab, Button } from "my-library-51";
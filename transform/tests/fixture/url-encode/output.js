@@ -0,0 +1,3 @@
+export { "foo bar" as fooBar } from "my-library-51/lib?member=foo%20bar";
+export { "a//b" as ab } from "my-library-51/lib?member=a%2F%2Fb";
+export { Button } from "my-library-51/lib?member=Button";
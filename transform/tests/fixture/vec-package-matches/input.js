@@ -0,0 +1,2 @@
+export { ArrowLeftIcon, CloseIcon } from "my-library-53/solid";
+export { ArrowLeftIcon as OutlineArrowLeft } from "my-library-53/outline";
@@ -0,0 +1,3 @@
+export * as ArrowLeftIcon from "my-library-53/solid/icons/arrow-left";
+export * as CloseIcon from "my-library-53/solid/icons/close";
+export * as OutlineArrowLeft from "my-library-53/outline/icons/arrow-left";
@@ -0,0 +1 @@
+export * from "my-library-4";
@@ -0,0 +1,602 @@
+use std::collections::HashMap;
+
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use modularize_exports::{
+    compile, modularize_exports, modularize_exports_preview, modularize_exports_tracked,
+    modularize_exports_with_stats, Config, FoldExports, MissingBehavior, MissingVariableBehavior,
+    PackageConfig, PlannedRewrite,
+};
+use swc_common::{
+    errors::{Handler, HANDLER},
+    DUMMY_SP,
+};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Fold, FoldWith};
+
+fn config() -> Config {
+    Config {
+        packages: vec![(
+            "react-bootstrap".to_string(),
+            PackageConfig {
+                transform: "react-bootstrap/lib/{{member}}".into(),
+                ..Default::default()
+            },
+        )],
+        ..Default::default()
+    }
+}
+
+fn named(name: &str) -> ExportSpecifier {
+    ExportSpecifier::Named(ExportNamedSpecifier {
+        span: DUMMY_SP,
+        orig: ModuleExportName::Ident(Ident::new(name.into(), DUMMY_SP)),
+        exported: None,
+        is_type_only: false,
+    })
+}
+
+fn export_from(specifiers: Vec<ExportSpecifier>, src: &str) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+        span: DUMMY_SP,
+        specifiers,
+        src: Some(Box::new(Str::from(src))),
+        type_only: false,
+        with: None,
+    }))
+}
+
+fn sources(module: &Module) -> Vec<&str> {
+    module
+        .body
+        .iter()
+        .map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) => {
+                &*decl.src.as_ref().unwrap().value
+            }
+            _ => panic!("expected a named export"),
+        })
+        .collect()
+}
+
+#[test]
+fn unhandled_specifier_keeps_rewritten_siblings() {
+    // `export Grid, { Row } from 'react-bootstrap'`
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(
+            vec![
+                named("Row"),
+                ExportSpecifier::Default(ExportDefaultSpecifier {
+                    exported: Ident::new("Grid".into(), DUMMY_SP),
+                }),
+                named("Col"),
+            ],
+            "react-bootstrap",
+        )],
+        shebang: None,
+    };
+
+    let module = module.fold_with(&mut modularize_exports(config()));
+
+    assert_eq!(
+        sources(&module),
+        [
+            "react-bootstrap/lib/Row",
+            "react-bootstrap",
+            "react-bootstrap/lib/Col"
+        ]
+    );
+}
+
+#[test]
+fn excluded_specifier_skips_prevent_full_export() {
+    // `export Grid, { Row } from 'react-bootstrap'`
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(
+            vec![
+                ExportSpecifier::Default(ExportDefaultSpecifier {
+                    exported: Ident::new("Grid".into(), DUMMY_SP),
+                }),
+                named("Row"),
+            ],
+            "react-bootstrap",
+        )],
+        shebang: None,
+    };
+    let config = Config {
+        packages: vec![(
+            "react-bootstrap".to_string(),
+            PackageConfig {
+                transform: "react-bootstrap/lib/{{member}}".into(),
+                prevent_full_export: true,
+                exclude: vec!["Grid".to_string()],
+                ..Default::default()
+            },
+        )],
+        ..Default::default()
+    };
+
+    let module = module.fold_with(&mut modularize_exports(config));
+
+    assert_eq!(
+        sources(&module),
+        ["react-bootstrap", "react-bootstrap/lib/Row"]
+    );
+}
+
+#[test]
+fn fold_exports_can_be_boxed_in_a_pipeline() {
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(vec![named("Row")], "react-bootstrap")],
+        shebang: None,
+    };
+    let mut pipeline: Vec<Box<dyn Fold>> = vec![Box::new(FoldExports::new(config()).unwrap())];
+
+    let module = pipeline
+        .iter_mut()
+        .fold(module, |module, fold| module.fold_with(fold));
+
+    assert_eq!(sources(&module), ["react-bootstrap/lib/Row"]);
+}
+
+fn helper_plural(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let param = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+    out.write(&format!("{}s", param))?;
+    Ok(())
+}
+
+#[test]
+fn custom_helpers_are_available_to_templates() {
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(vec![named("Row")], "react-bootstrap")],
+        shebang: None,
+    };
+    let config = Config {
+        packages: vec![(
+            "react-bootstrap".to_string(),
+            PackageConfig {
+                transform: "react-bootstrap/{{ plural (lowerCase member) }}/{{ member }}".into(),
+                ..Default::default()
+            },
+        )],
+        ..Default::default()
+    };
+    let mut folder = FoldExports::new(config)
+        .unwrap()
+        .with_helper("plural", Box::new(helper_plural));
+
+    let module = module.fold_with(&mut folder);
+
+    assert_eq!(sources(&module), ["react-bootstrap/rows/Row"]);
+}
+
+#[test]
+fn missing_or_empty_source_is_never_rewritten() {
+    // `export {}`, and an `export { Row } from ''` another transform could produce.
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                span: DUMMY_SP,
+                specifiers: vec![],
+                src: None,
+                type_only: false,
+                with: None,
+            })),
+            export_from(vec![named("Row")], ""),
+        ],
+        shebang: None,
+    };
+    let config = Config {
+        packages: vec![(
+            ".*".to_string(),
+            PackageConfig {
+                transform: "lib/{{ member }}".into(),
+                ..Default::default()
+            },
+        )],
+        ..Default::default()
+    };
+
+    let folded = module.clone().fold_with(&mut modularize_exports(config));
+
+    assert_eq!(folded, module);
+}
+
+#[test]
+fn dirty_handle_tracks_rewritten_exports() {
+    let (mut folder, dirty) = modularize_exports_tracked(config());
+    let module = |src: &str| Module {
+        span: DUMMY_SP,
+        body: vec![export_from(vec![named("Button")], src)],
+        shebang: None,
+    };
+
+    module("./local").fold_with(&mut folder);
+    assert!(!dirty.is_dirty());
+
+    module("react-bootstrap").fold_with(&mut folder);
+    assert!(dirty.is_dirty());
+
+    dirty.reset();
+    module("./local").fold_with(&mut folder);
+    assert!(!dirty.is_dirty());
+}
+
+#[test]
+fn preview_records_rewrites_without_changing_the_module() {
+    let (mut folder, preview) = modularize_exports_preview(config());
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![
+            export_from(vec![named("Button"), named("Alert")], "react-bootstrap"),
+            export_from(vec![named("Local")], "./local"),
+        ],
+        shebang: None,
+    };
+
+    let folded = module.clone().fold_with(&mut folder);
+
+    assert_eq!(folded, module);
+    assert_eq!(
+        preview.take(),
+        [
+            PlannedRewrite {
+                src: "react-bootstrap".to_string(),
+                member: "Button".to_string(),
+                new_src: "react-bootstrap/lib/Button".to_string(),
+            },
+            PlannedRewrite {
+                src: "react-bootstrap".to_string(),
+                member: "Alert".to_string(),
+                new_src: "react-bootstrap/lib/Alert".to_string(),
+            },
+        ]
+    );
+    assert!(preview.rewrites().is_empty());
+}
+
+#[test]
+fn stats_count_rewritten_members_per_package() {
+    let (mut folder, stats) = modularize_exports_with_stats(config());
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![
+            export_from(vec![named("Button"), named("Alert")], "react-bootstrap"),
+            export_from(vec![named("Local")], "./local"),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+                span: DUMMY_SP,
+                src: Box::new(Str::from("react-bootstrap")),
+                type_only: false,
+                with: None,
+            })),
+        ],
+        shebang: None,
+    };
+
+    module.fold_with(&mut folder);
+
+    assert_eq!(
+        stats.counts(),
+        HashMap::from([("react-bootstrap".to_string(), 3)])
+    );
+    stats.reset();
+    assert!(stats.counts().is_empty());
+}
+
+fn known_members(exclude: &[&str]) -> (Module, Config) {
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+            span: DUMMY_SP,
+            src: Box::new(Str::from("react-bootstrap")),
+            type_only: false,
+            with: None,
+        }))],
+        shebang: None,
+    };
+    let config = Config {
+        packages: vec![(
+            "react-bootstrap".to_string(),
+            PackageConfig {
+                transform: "react-bootstrap/lib/{{member}}".into(),
+                known_members: Some(vec!["Button".to_string(), "Alert".to_string()]),
+                exclude: exclude.iter().map(|x| x.to_string()).collect(),
+                ..Default::default()
+            },
+        )],
+        ..Default::default()
+    };
+    (module, config)
+}
+
+#[test]
+fn preview_keeps_export_all_with_known_members() {
+    let (module, config) = known_members(&[]);
+    let (mut folder, preview) = modularize_exports_preview(config);
+
+    let folded = module.clone().fold_with(&mut folder);
+
+    assert_eq!(folded, module);
+    let members: Vec<String> = preview.take().into_iter().map(|x| x.member).collect();
+    assert_eq!(members, ["Button", "Alert"]);
+}
+
+#[test]
+fn excluding_every_known_member_keeps_export_all() {
+    let (module, config) = known_members(&["Button", "Alert"]);
+    let (mut folder, dirty) = modularize_exports_tracked(config.clone());
+
+    assert_eq!(module.clone().fold_with(&mut folder), module);
+    assert!(!dirty.is_dirty());
+
+    let (mut folder, stats) = modularize_exports_with_stats(config);
+    module.fold_with(&mut folder);
+    assert!(stats.counts().is_empty());
+}
+
+#[test]
+fn excluding_some_known_members_counts_the_others() {
+    let (module, config) = known_members(&["Alert"]);
+    let (mut folder, dirty) = modularize_exports_tracked(config.clone());
+
+    let folded = module.clone().fold_with(&mut folder);
+    assert_eq!(
+        sources(&folded),
+        ["react-bootstrap/lib/Button", "react-bootstrap"]
+    );
+    assert!(dirty.is_dirty());
+
+    let (mut folder, stats) = modularize_exports_with_stats(config);
+    module.fold_with(&mut folder);
+    assert_eq!(
+        stats.counts(),
+        HashMap::from([("react-bootstrap".to_string(), 1)])
+    );
+}
+
+#[test]
+fn compiled_config_is_shared_by_many_folders() {
+    let compiled = compile(config()).unwrap();
+    let module = |src: &str| Module {
+        span: DUMMY_SP,
+        body: vec![export_from(vec![named("Button")], src)],
+        shebang: None,
+    };
+
+    let mut first = compiled.folder();
+    let mut second = compiled.folder();
+    let dirty = second.dirty();
+
+    let folded = module("react-bootstrap").fold_with(&mut first);
+    assert_eq!(sources(&folded), ["react-bootstrap/lib/Button"]);
+    assert!(!dirty.is_dirty());
+
+    let folded = module("react-bootstrap").fold_with(&mut second);
+    assert_eq!(sources(&folded), ["react-bootstrap/lib/Button"]);
+    assert!(dirty.is_dirty());
+}
+
+#[test]
+fn package_keys_are_anchored_on_both_sides() {
+    let rewrites = |key: &str, src: &str| {
+        let mut config = config();
+        config.packages[0].0 = key.to_string();
+        let module = Module {
+            span: DUMMY_SP,
+            body: vec![export_from(vec![named("Button")], src)],
+            shebang: None,
+        };
+        let module = module.fold_with(&mut modularize_exports(config));
+        sources(&module) != [src]
+    };
+
+    for key in ["^foo", "foo$", "^foo$", "foo"] {
+        assert!(rewrites(key, "foo"), "{} should match foo", key);
+        assert!(
+            !rewrites(key, "foo-extra"),
+            "{} should not match foo-extra",
+            key
+        );
+        assert!(
+            !rewrites(key, "extra-foo"),
+            "{} should not match extra-foo",
+            key
+        );
+    }
+
+    for key in ["react|preact", "^react|preact$"] {
+        assert!(rewrites(key, "react"), "{} should match react", key);
+        assert!(rewrites(key, "preact"), "{} should match preact", key);
+        assert!(
+            !rewrites(key, "react-dom"),
+            "{} should not match react-dom",
+            key
+        );
+        assert!(
+            !rewrites(key, "not-preact"),
+            "{} should not match not-preact",
+            key
+        );
+    }
+    assert!(rewrites("foo\\$", "foo$"));
+    assert!(!rewrites("foo\\$", "foo$-extra"));
+}
+
+#[test]
+fn vec_patterns_are_anchored_around_alternations() {
+    let mut config = config();
+    config.packages[0].1.transform = vec![(
+        "Button|Card".to_string(),
+        "react-bootstrap/lib/{{member}}".to_string(),
+    )]
+    .into();
+    config.packages[0].1.on_missing = MissingBehavior::Keep;
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(
+            vec![
+                named("Button"),
+                named("ButtonGroup"),
+                named("Card"),
+                named("MyCard"),
+            ],
+            "react-bootstrap",
+        )],
+        shebang: None,
+    };
+
+    let module = module.fold_with(&mut modularize_exports(config));
+
+    assert_eq!(
+        sources(&module),
+        [
+            "react-bootstrap/lib/Button",
+            "react-bootstrap",
+            "react-bootstrap/lib/Card",
+            "react-bootstrap"
+        ]
+    );
+}
+
+fn full_export() -> (Module, Config) {
+    // `export Grid, { Row } from 'react-bootstrap'`
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(
+            vec![
+                ExportSpecifier::Default(ExportDefaultSpecifier {
+                    exported: Ident::new("Grid".into(), DUMMY_SP),
+                }),
+                named("Row"),
+            ],
+            "react-bootstrap",
+        )],
+        shebang: None,
+    };
+    let mut config = config();
+    config.packages[0].1.prevent_full_export = true;
+    (module, config)
+}
+
+#[test]
+fn prevent_full_export_reports_an_error_and_keeps_the_export() {
+    let (module, config) = full_export();
+    let handler = Handler::with_emitter_writer(Box::new(std::io::sink()), None);
+
+    let folded = HANDLER.set(&handler, || {
+        module.clone().fold_with(&mut modularize_exports(config))
+    });
+
+    assert!(handler.has_errors());
+    assert_eq!(folded, module);
+}
+
+#[test]
+fn prevent_full_export_is_recorded_without_a_handler() {
+    let (module, config) = full_export();
+    let mut folder = modularize_exports(config);
+    let errors = folder.errors();
+
+    let folded = module.clone().fold_with(&mut folder);
+
+    assert_eq!(folded, module);
+    let errors = errors.take();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "export 'Grid' of package 'react-bootstrap' causes the entire module to be exported"
+    );
+}
+
+#[test]
+fn double_slashes_survive_without_normalize_slashes() {
+    let rewrite = |normalize_slashes: bool| {
+        let mut config = config();
+        config.packages[0].1.transform = "//server/react-bootstrap//lib/{{member}}".into();
+        config.normalize_slashes = normalize_slashes;
+        let module = Module {
+            span: DUMMY_SP,
+            body: vec![export_from(vec![named("Row")], "react-bootstrap")],
+            shebang: None,
+        };
+        let module = module.fold_with(&mut modularize_exports(config));
+        sources(&module)[0].to_string()
+    };
+
+    assert_eq!(rewrite(true), "/server/react-bootstrap/lib/Row");
+    assert_eq!(rewrite(false), "//server/react-bootstrap//lib/Row");
+}
+
+fn out_of_range_capture(
+    transform: &str,
+    on_missing_variable: MissingVariableBehavior,
+) -> (Module, Module, bool) {
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![export_from(vec![named("Button")], "react-bootstrap")],
+        shebang: None,
+    };
+    let config = Config {
+        packages: vec![(
+            "react-bootstrap".to_string(),
+            PackageConfig {
+                transform: transform.into(),
+                member_pattern: Some("(\\w+)".to_string()),
+                ..Default::default()
+            },
+        )],
+        on_missing_variable,
+        ..Default::default()
+    };
+    let handler = Handler::with_emitter_writer(Box::new(std::io::sink()), None);
+
+    let folded = HANDLER.set(&handler, || {
+        module.clone().fold_with(&mut modularize_exports(config))
+    });
+    (module, folded, handler.has_errors())
+}
+
+#[test]
+fn out_of_range_captures_render_empty_by_default() {
+    for transform in [
+        "react-bootstrap/{{ matches.[3] }}/{{ member }}",
+        "react-bootstrap/{{ memberMatches.[5] }}/{{ member }}",
+    ] {
+        let (_, folded, has_errors) =
+            out_of_range_capture(transform, MissingVariableBehavior::Empty);
+
+        assert!(!has_errors);
+        assert_eq!(sources(&folded), ["react-bootstrap/Button"]);
+    }
+}
+
+#[test]
+fn out_of_range_captures_fail_the_export_on_error() {
+    for transform in [
+        "react-bootstrap/{{ matches.[3] }}/{{ member }}",
+        "react-bootstrap/{{ memberMatches.[5] }}/{{ member }}",
+    ] {
+        let (module, folded, has_errors) =
+            out_of_range_capture(transform, MissingVariableBehavior::Error);
+
+        assert!(has_errors);
+        assert_eq!(folded, module);
+    }
+
+    let (_, folded, has_errors) = out_of_range_capture(
+        "react-bootstrap/{{ memberMatches.[1] }}",
+        MissingVariableBehavior::Error,
+    );
+    assert!(!has_errors);
+    assert_eq!(sources(&folded), ["react-bootstrap/Button"]);
+}
@@ -0,0 +1,2 @@
+export { Button, Row } from "react-bootstrap";
+export * from "react-bootstrap";
@@ -0,0 +1,3 @@
+export * as Button from 'react-bootstrap/lib/Button';
+export * as Row from 'react-bootstrap/lib/Row';
+export * from 'react-bootstrap/lib/*';
@@ -0,0 +1,2 @@
+export { Row, Button, Alert } from 'react-bootstrap';
+export { Accordion } from 'react-bootstrap';
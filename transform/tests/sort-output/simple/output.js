@@ -0,0 +1,4 @@
+export * as Alert from "react-bootstrap/lib/Alert";
+export * as Button from "react-bootstrap/lib/Button";
+export * as Row from "react-bootstrap/lib/Row";
+export * as Accordion from "react-bootstrap/lib/Accordion";
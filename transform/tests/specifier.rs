@@ -0,0 +1,55 @@
+use modularize_exports::{renderer, transform_specifier, PackageConfig, TransformError};
+
+#[test]
+fn transform_specifier_renders_string_transform() {
+    let config = PackageConfig {
+        transform: "my-library/{{ matches.[1] }}/{{ kebabCase member }}".into(),
+        ..Default::default()
+    };
+
+    let path = transform_specifier(
+        &config,
+        &renderer(),
+        "my-library/components",
+        "MyButton",
+        &["my-library/components", "components"],
+    )
+    .unwrap();
+    assert_eq!(path, "my-library/components/my-button");
+}
+
+#[test]
+fn transform_specifier_renders_first_matching_vec_rule() {
+    let config = PackageConfig {
+        transform: Vec::from([
+            (
+                "use(\\w*)".to_string(),
+                "my-library/hooks/{{ kebabCase memberMatches.[1] }}".to_string(),
+            ),
+            ("(\\w*)".to_string(), "my-library/{{ member }}".to_string()),
+        ])
+        .into(),
+        ..Default::default()
+    };
+
+    let renderer = renderer();
+    let render = |member| transform_specifier(&config, &renderer, "my-library", member, &[]);
+    assert_eq!(render("useTheme").unwrap(), "my-library/hooks/theme");
+    assert_eq!(render("Button").unwrap(), "my-library/Button");
+}
+
+#[test]
+fn transform_specifier_reports_missing_transform() {
+    let config = PackageConfig {
+        transform: Vec::from([("foo".to_string(), "my-library/foo".to_string())]).into(),
+        ..Default::default()
+    };
+
+    match transform_specifier(&config, &renderer(), "my-library", "bar", &[]) {
+        Err(TransformError::MissingTransform { package, member }) => {
+            assert_eq!(package, "my-library");
+            assert_eq!(member, "bar");
+        }
+        _ => panic!("expected a missing transform error"),
+    }
+}
@@ -0,0 +1,3 @@
+export type * from 'react-bootstrap';
+export type * as Types from 'react-bootstrap';
+export * from 'react-bootstrap';
@@ -0,0 +1,6 @@
+export type { Props, Variant } from 'react-bootstrap';
+export { Button, type ButtonProps as Props2, Alert as Notice } from 'react-bootstrap';
+export interface Local {
+    size: number;
+}
+export type Size = 'sm' | 'lg';
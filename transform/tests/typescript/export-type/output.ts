@@ -0,0 +1,8 @@
+export type { Props, Variant } from 'react-bootstrap';
+export * as Button from "react-bootstrap/lib/Button";
+export { type ButtonProps as Props2 } from 'react-bootstrap';
+export * as Notice from "react-bootstrap/lib/Alert";
+export interface Local {
+    size: number;
+}
+export type Size = 'sm' | 'lg';
@@ -0,0 +1,5 @@
+import type { Theme } from 'react-bootstrap';
+import { Card } from 'react-bootstrap';
+export type { Theme };
+export { Card };
+export * as Modal from "react-bootstrap/lib/Modal";
@@ -0,0 +1 @@
+export { type Props, Button } from 'react-bootstrap';